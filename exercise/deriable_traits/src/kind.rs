@@ -0,0 +1,80 @@
+use crate::PuzzleType;
+use std::fmt::Debug;
+use std::io;
+use thiserror::Error;
+
+/// The rules of one kind of puzzle
+///
+/// A kind owns its state and checks every move against it, so the code that
+/// hosts a game ([`Session`](crate::Session)) only needs this trait to play,
+/// undo, save and load any kind.
+pub trait PuzzleKind: Sized {
+    /// One step a player can take
+    type Move: Copy + Debug + PartialEq;
+
+    /// Which kind this is, written at the top of saved games
+    const TYPE: PuzzleType;
+
+    /// Check `mv` and make it, returning the move that takes it back
+    fn apply(&mut self, mv: Self::Move) -> Result<Self::Move, MoveError>;
+
+    fn is_solved(&self) -> bool;
+
+    /// The state as text that [`PuzzleKind::decode`] reads back
+    fn encode(&self) -> String;
+
+    fn decode(text: &str) -> Result<Self, BoardError>;
+}
+
+/// A move the rules don't allow
+#[derive(Debug, Error, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum MoveError {
+    #[error("There is no tile on that side of the gap")]
+    NoTile,
+    #[error("Row {row}, column {col} is outside the puzzle")]
+    OffBoard { row: usize, col: usize },
+    #[error("Row {row}, column {col} is part of the puzzle and can't be changed")]
+    Given { row: usize, col: usize },
+    #[error("{0} is not a sudoku digit")]
+    BadDigit(u8),
+    #[error("{digit} is already in that row, column or box")]
+    Conflict { digit: u8 },
+}
+
+/// A puzzle that can't be set up, because its size, layout or clues are wrong
+#[derive(Debug, Error, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BoardError {
+    #[error("A {kind} puzzle can't be {size} across")]
+    BadSize { kind: PuzzleType, size: usize },
+    #[error("The tiles must be 0 to {max}, each exactly once")]
+    BadTiles { max: usize },
+    #[error("Line {line}: {reason}")]
+    BadLine { line: usize, reason: String },
+    #[error("The givens break the rules: {digit} appears twice in a row, column or box")]
+    ConflictingGivens { digit: u8 },
+    #[error("Clue {clue:?} doesn't fit in {length} cells")]
+    ClueTooLong { clue: Vec<usize>, length: usize },
+}
+
+/// Why a saved game couldn't be loaded
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum LoadError {
+    #[error("Couldn't read the saved game")]
+    Read(#[source] io::Error),
+    #[error("The saved game doesn't say what kind of puzzle it is")]
+    MissingType,
+    #[error("Unknown puzzle kind {0:?}")]
+    UnknownType(String),
+    #[error("{0} puzzles can't be saved, so there is no game to load")]
+    NotSaveable(PuzzleType),
+    #[error("Expected a {expected} puzzle but the save holds a {found} puzzle")]
+    WrongType {
+        expected: PuzzleType,
+        found: PuzzleType,
+    },
+    #[error("The saved puzzle is invalid")]
+    Board(#[from] BoardError),
+}
@@ -0,0 +1,230 @@
+// 상속(derive) 매크로가 정의된 트레잇은 상속해서 사용할 수 있습니다.
+use puzzles::{Profile, PuzzleError};
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
+mod kind;
+mod nonogram;
+mod pattern_db;
+mod puzzle_set;
+mod session;
+mod sliding;
+mod sliding_solver;
+mod sudoku;
+
+pub use kind::{BoardError, LoadError, MoveError, PuzzleKind};
+pub use nonogram::{Mark, Nonogram, Paint, MAX_NONOGRAM_SIDE};
+pub use pattern_db::{PatternDb, PatternError};
+pub use puzzle_set::PuzzleSet;
+pub use session::{saved_type, Session};
+pub use sliding::{Slide, SlidingTile, SLIDING_SIZES};
+pub use sliding_solver::{SearchStats, SlideSolution, SlidingSolver, SolveError};
+pub use sudoku::{Fill, Sudoku};
+
+// Debug
+// struct나 enum의 모든 필드가 Debug로 되어있다면,
+// struct와 enum에도 Debug를 상속할 수 있습니다
+// Debug를 상속하면 `디버그 포맷` 과 `정돈된 디버그 포맷`을 사용할 수 있습니다
+
+// Clone
+// clone() 메서드를 호출해 값을 복제할 수 있습니다
+// struct 나 enum의 모든 필드가 Clone을 상속받았으면
+// 전체에 Clone을 상속할 수 있습니다
+
+// Copy
+// Clone과 밀접하게 연관되어있습니다. 특별한 marker trait 입니다
+// 타입에 Copy가 구현돼 있으면, 해당 타입의 값을 넘겨줄 때 이동이 아니라 복사가 됩니다
+// 그렇기 때문에 스택 영역에 맞는 작은 값에 쓰는 게 좋습니다
+// 힙 영역만 사용하는 타입에는 Copy를 구현하면 안됩니다.
+// Puzzle 에는 Copy를 구현할 수 없는데, String 타입은 Copy가 아니기 때문에 불가능합니다.
+#[derive(Debug, Clone)]
+pub struct Puzzle {
+    pub num_pieces: u32,
+    pub name: String,
+}
+
+// Unit 타입의 열거값만 있습니다.
+// Unit 타입은 Copy 입니다.
+// 그래서 해당 enum은 Copy를 상속받을 수 있습니다
+// 하지만 Copy만 상속받으면 컴파일 되지않습니다.
+// Copy는 Clone의 하위 trait이기 떄문입니다.
+// trait을 구현할 때는 그 부모 trait 을 함게 구현해야 합니다
+// 소량의 데이터를 가진 작은 struct나 enum의 경우 값을 참조하고, 이동시키는 것 보다
+// 복사하는게 더 빠릅니다.
+// 퍼즐 종류마다 규칙은 PuzzleKind trait 을 구현한 타입에 따로 있습니다. (Jigsaw 는 puzzles crate 에 있어요)
+// 여기에는 이름만 있어서 Copy 로 가볍게 주고받을 수 있습니다.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum PuzzleType {
+    Jigsaw,
+    /// The 15 puzzle and its relatives, see [`SlidingTile`]
+    SlidingTile,
+    Sudoku,
+    Nonogram,
+}
+
+impl PuzzleType {
+    pub const ALL: [PuzzleType; 4] = [
+        PuzzleType::Jigsaw,
+        PuzzleType::SlidingTile,
+        PuzzleType::Sudoku,
+        PuzzleType::Nonogram,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            PuzzleType::Jigsaw => "jigsaw",
+            PuzzleType::SlidingTile => "sliding-tile",
+            PuzzleType::Sudoku => "sudoku",
+            PuzzleType::Nonogram => "nonogram",
+        }
+    }
+}
+
+impl fmt::Display for PuzzleType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+impl FromStr for PuzzleType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        PuzzleType::ALL
+            .into_iter()
+            .find(|kind| kind.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("unknown puzzle type {:?}", s))
+    }
+}
+
+// Trait 을 직접 구현하는 건 3단계로 구성돼 있습니다.
+// 첫 번째 use 문을 사용해 trait 을 범위안으로 가지고 오기
+// 두 번째 Boilerplate
+// 세 번째 Implementation
+
+// Default
+// 전역 기본 프로필과 상관없이, 이 예제의 기본값은 언제나 30 조각짜리 "Forest Lake" 입니다.
+impl Default for Puzzle {
+    fn default() -> Self {
+        Puzzle {
+            num_pieces: 30,
+            name: "Forest Lake".to_string(),
+        }
+    }
+}
+
+// PartialEq
+// Eq는 마커(std::marker) trait 으로 등식이 반사적, 전이적, 대칭적으로
+// 성립할 때 구현할 수 있습니다.
+impl PartialEq for Puzzle {
+    // self 는 self: &Self 로 풀어쓸 수 있습니다.
+    // 타입은 Self 구조체 값에 대한 불변 참조 타입입니다.
+    // Self를 해당 구조체 이름으로 바꿔 쓰며 Puzzle 이 되겠죠
+    // PartialEq와 Eq를 모두 구현할 건지 PartialEq만 구현할 건지에 따라 다릅니다.
+    fn eq(&self, other: &Self) -> bool {
+        (self.num_pieces == other.num_pieces) && (self.folded_name() == other.folded_name())
+    }
+}
+
+// Eq
+// Eq 륽 구현해서 얻는 장점은 많지는 않다.
+// 해시맵에서 Puzzle 을 키로 사용할 수 있다는 것 정도입니다.
+impl Eq for Puzzle {}
+
+// Hash
+// 해시맵의 키가 되려면 Hash 도 필요합니다.
+// 규칙은 하나, a == b 이면 hash(a) == hash(b) 여야 합니다.
+// derive 하면 name 을 그대로 해시하니 "Forest Lake" 와 "forest lake" 가 같은데도
+// 해시가 달라져서 해시맵에 둘 다 들어가 버립니다.
+// 그래서 eq 가 비교하는 것과 똑같이 소문자로 바꾼 이름을 해시합니다.
+impl Hash for Puzzle {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.num_pieces.hash(state);
+        self.folded_name().hash(state);
+    }
+}
+
+// Ord
+// 정렬 순서도 eq 와 어긋나면 안 됩니다. cmp 가 Equal 일 때만 == 이어야 하죠.
+// 이름(대소문자 무시) 순으로, 이름이 같으면 조각 수 순으로 놓습니다.
+impl Ord for Puzzle {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.folded_name()
+            .cmp(&other.folded_name())
+            .then(self.num_pieces.cmp(&other.num_pieces))
+    }
+}
+
+// Ord 를 구현하면 PartialOrd 는 cmp 를 그대로 쓰면 됩니다.
+impl PartialOrd for Puzzle {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Puzzle {
+    /// The name as `eq`, `hash` and `cmp` see it
+    // 세 trait 이 모두 이 함수 하나만 보도록 해서 서로 어긋날 일을 없앱니다.
+    fn folded_name(&self) -> String {
+        self.name.to_lowercase()
+    }
+}
+
+// From  From<T> for U
+// From<Puzzle> for String
+// From 을 구현하면 Into 는 자동으로 구현됩니다
+// 하지만 Rust에서 자주 사용되는 건 Into trait과 제네릭 함수를 조합해서 사용하는 거죠
+impl From<&Puzzle> for String {
+    // 여기서도 복제를 하지만, Puzzle 전체를 복제 하는것 보다 훨씬 낫다.
+    fn from(puzzle: &Puzzle) -> Self {
+        puzzle.name.clone()
+    }
+}
+
+// 퍼즐의 기준 모델은 puzzles crate 에 있고, 여기 Puzzle 은 이름과 조각 수만 보는 가벼운 모습입니다.
+// 기준 모델에서 이쪽으로는 언제나 바꿀 수 있으니 From 을 구현합니다.
+impl From<&puzzles::Puzzle> for Puzzle {
+    fn from(puzzle: &puzzles::Puzzle) -> Self {
+        Puzzle {
+            num_pieces: puzzle.num_pieces,
+            name: puzzle.name.clone(),
+        }
+    }
+}
+
+impl From<&Profile> for Puzzle {
+    fn from(profile: &Profile) -> Self {
+        Puzzle {
+            num_pieces: profile.num_pieces(),
+            name: profile.name.clone(),
+        }
+    }
+}
+
+// 거꾸로 갈 때는 조각 수를 판에 늘어놓을 수 있어야 하니 실패할 수 있습니다.
+// 그래서 From 대신 TryFrom 을 구현하고, 실패하면 그 이유를 Error 타입으로 돌려줍니다.
+impl TryFrom<&Puzzle> for Profile {
+    type Error = PuzzleError;
+
+    fn try_from(puzzle: &Puzzle) -> Result<Self, Self::Error> {
+        Profile::for_pieces(puzzle.name.clone(), puzzle.num_pieces)
+    }
+}
+
+impl TryFrom<&Puzzle> for puzzles::Puzzle {
+    type Error = PuzzleError;
+
+    fn try_from(puzzle: &Puzzle) -> Result<Self, Self::Error> {
+        puzzles::Puzzle::try_from(Profile::try_from(puzzle)?)
+    }
+}
+
+// Into  Into<U> for T
+// Into<String> for Puzzle
+
+// 인수로 T 타입의 's'를 받습니다. T는 Into<String>이 구현된 타입이면 뭐든지 가능합니다
+pub fn show<T: Into<String>>(s: T) {
+    println!("{}", s.into());
+}
@@ -0,0 +1,319 @@
+use crate::{BoardError, MoveError, PuzzleKind, PuzzleType};
+use std::fmt;
+
+/// Longest row or column a nonogram may have
+pub const MAX_NONOGRAM_SIDE: usize = 50;
+
+/// What the player has put in a cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Mark {
+    #[default]
+    Empty,
+    Filled,
+    /// Known to stay empty; counts as empty for the clues
+    Crossed,
+}
+
+impl Mark {
+    fn symbol(self) -> char {
+        match self {
+            Mark::Empty => '.',
+            Mark::Filled => '#',
+            Mark::Crossed => 'x',
+        }
+    }
+}
+
+/// Put `mark` in a cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Paint {
+    pub row: usize,
+    pub col: usize,
+    pub mark: Mark,
+}
+
+/// A picture puzzle where every row and column says how long its runs of filled cells are
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Nonogram {
+    rows: Vec<Vec<usize>>,
+    cols: Vec<Vec<usize>>,
+    cells: Vec<Mark>,
+}
+
+impl Nonogram {
+    /// An empty grid with clues for each row, top to bottom, and each column, left to right
+    pub fn new(rows: Vec<Vec<usize>>, cols: Vec<Vec<usize>>) -> Result<Self, BoardError> {
+        for side in [rows.len(), cols.len()] {
+            if !(1..=MAX_NONOGRAM_SIDE).contains(&side) {
+                return Err(BoardError::BadSize {
+                    kind: PuzzleType::Nonogram,
+                    size: side,
+                });
+            }
+        }
+        // 한 줄의 단서는 칸을 하나씩 띄워 놓았을 때 그 줄에 들어가야 합니다.
+        for (clues, length) in [(&rows, cols.len()), (&cols, rows.len())] {
+            for clue in clues {
+                let needed = clue.iter().sum::<usize>() + clue.len().saturating_sub(1);
+                if needed > length || clue.contains(&0) {
+                    return Err(BoardError::ClueTooLong {
+                        clue: clue.clone(),
+                        length,
+                    });
+                }
+            }
+        }
+        Ok(Self {
+            cells: vec![Mark::Empty; rows.len() * cols.len()],
+            rows,
+            cols,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.cols.len()
+    }
+
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn row_clue(&self, row: usize) -> &[usize] {
+        &self.rows[row]
+    }
+
+    pub fn col_clue(&self, col: usize) -> &[usize] {
+        &self.cols[col]
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Option<Mark> {
+        (row < self.height() && col < self.width()).then(|| self.cells[row * self.width() + col])
+    }
+}
+
+/// Lengths of the runs of filled cells, in order
+fn runs(line: impl Iterator<Item = Mark>) -> Vec<usize> {
+    let mut runs = Vec::new();
+    let mut length = 0;
+    for mark in line.chain([Mark::Empty]) {
+        if mark == Mark::Filled {
+            length += 1;
+        } else if length > 0 {
+            runs.push(length);
+            length = 0;
+        }
+    }
+    runs
+}
+
+fn write_clues(f: &mut fmt::Formatter, label: &str, clues: &[Vec<usize>]) -> fmt::Result {
+    let clues: Vec<_> = clues
+        .iter()
+        .map(|clue| match clue.as_slice() {
+            [] => "0".to_string(),
+            _ => clue
+                .iter()
+                .map(usize::to_string)
+                .collect::<Vec<_>>()
+                .join(" "),
+        })
+        .collect();
+    writeln!(f, "{}: {}", label, clues.join(", "))
+}
+
+/// Read a `label: 1 2, 3` line. `end` is the line number to blame if it is missing.
+fn parse_clues(
+    line: Option<(usize, &str)>,
+    label: &str,
+    end: usize,
+) -> Result<Vec<Vec<usize>>, BoardError> {
+    let (i, line) = line.ok_or_else(|| BoardError::BadLine {
+        line: end,
+        reason: format!("the {} clues are missing", label),
+    })?;
+    let bad = |reason: String| BoardError::BadLine {
+        line: i + 1,
+        reason,
+    };
+    let clues = line
+        .strip_prefix(label)
+        .and_then(|rest| rest.strip_prefix(':'))
+        .ok_or_else(|| bad(format!("expected the {} clues", label)))?;
+    clues
+        .split(',')
+        .map(|clue| {
+            clue.split_whitespace()
+                .map(|n| {
+                    n.parse()
+                        .map_err(|_| bad(format!("{:?} is not a run length", n)))
+                })
+                // 빈 줄은 "0" 으로 씁니다.
+                .filter(|n| *n != Ok(0))
+                .collect()
+        })
+        .collect()
+}
+
+impl PuzzleKind for Nonogram {
+    type Move = Paint;
+
+    const TYPE: PuzzleType = PuzzleType::Nonogram;
+
+    fn apply(&mut self, paint: Paint) -> Result<Paint, MoveError> {
+        let Paint { row, col, mark } = paint;
+        let before = self.get(row, col).ok_or(MoveError::OffBoard { row, col })?;
+        let width = self.width();
+        self.cells[row * width + col] = mark;
+        Ok(Paint {
+            mark: before,
+            ..paint
+        })
+    }
+
+    fn is_solved(&self) -> bool {
+        let width = self.width();
+        let rows_match = self
+            .cells
+            .chunks(width)
+            .map(|row| runs(row.iter().copied()))
+            .eq(self.rows.iter().cloned());
+        let cols_match = (0..width)
+            .map(|col| runs(self.cells.iter().skip(col).step_by(width).copied()))
+            .eq(self.cols.iter().cloned());
+        rows_match && cols_match
+    }
+
+    /// The row clues, the column clues and then the grid, with `#` for filled and `x` for crossed
+    fn encode(&self) -> String {
+        self.to_string()
+    }
+
+    fn decode(text: &str) -> Result<Self, BoardError> {
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i, line.trim()))
+            .filter(|(_, line)| !line.is_empty());
+        let end = text.lines().count() + 1;
+        let rows = parse_clues(lines.next(), "rows", end)?;
+        let cols = parse_clues(lines.next(), "cols", end)?;
+        let mut nonogram = Nonogram::new(rows, cols)?;
+        // 그림은 없어도 됩니다. 아직 아무 칸도 칠하지 않은 게임이죠.
+        let width = nonogram.width();
+        for (row, (i, line)) in lines.enumerate() {
+            let bad = |reason: String| BoardError::BadLine {
+                line: i + 1,
+                reason,
+            };
+            if row >= nonogram.height() {
+                return Err(bad("too many rows".to_string()));
+            }
+            if line.chars().count() != width {
+                return Err(bad(format!(
+                    "expected {} cells, found {}",
+                    width,
+                    line.chars().count()
+                )));
+            }
+            for (col, c) in line.chars().enumerate() {
+                nonogram.cells[row * width + col] = match c {
+                    '.' => Mark::Empty,
+                    '#' => Mark::Filled,
+                    'x' => Mark::Crossed,
+                    _ => return Err(bad(format!("{:?} is not a mark", c))),
+                };
+            }
+        }
+        Ok(nonogram)
+    }
+}
+
+impl fmt::Display for Nonogram {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_clues(f, "rows", &self.rows)?;
+        write_clues(f, "cols", &self.cols)?;
+        for row in self.cells.chunks(self.width()) {
+            let line: String = row.iter().map(|mark| mark.symbol()).collect();
+            writeln!(f, "{}", line)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // 작은 하트 모양
+    const HEART: &str = "\
+rows: 1 1, 5, 5, 3, 1
+cols: 2, 4, 4, 4, 2
+";
+
+    fn paint(row: usize, col: usize, mark: Mark) -> Paint {
+        Paint { row, col, mark }
+    }
+
+    #[test]
+    fn solved_when_every_clue_matches() {
+        let mut heart = Nonogram::decode(HEART).unwrap();
+        let picture = [".#.#.", "#####", "#####", ".###.", "..#.."];
+        for (row, line) in picture.iter().enumerate() {
+            for (col, c) in line.chars().enumerate() {
+                // x 는 빈칸과 같으니, 마지막 # 을 칠하는 순간 풀립니다.
+                let mark = if c == '#' {
+                    assert!(!heart.is_solved());
+                    Mark::Filled
+                } else {
+                    Mark::Crossed
+                };
+                heart.apply(paint(row, col, mark)).unwrap();
+            }
+        }
+        assert!(heart.is_solved());
+        assert!(heart
+            .encode()
+            .ends_with("x#x#x\n#####\n#####\nx###x\nxx#xx\n"));
+
+        let back = heart.apply(paint(4, 2, Mark::Empty)).unwrap();
+        assert_eq!(back, paint(4, 2, Mark::Filled));
+        assert!(!heart.is_solved());
+        assert_eq!(
+            heart.apply(paint(5, 0, Mark::Filled)),
+            Err(MoveError::OffBoard { row: 5, col: 0 })
+        );
+    }
+
+    #[test]
+    fn round_trips_through_text() {
+        let mut heart = Nonogram::decode(HEART).unwrap();
+        heart.apply(paint(1, 1, Mark::Filled)).unwrap();
+        heart.apply(paint(0, 0, Mark::Crossed)).unwrap();
+        let text = heart.encode();
+        assert!(text.starts_with(HEART));
+        assert_eq!(Nonogram::decode(&text).unwrap(), heart);
+
+        let empty = Nonogram::decode("rows: 0, 1\ncols: 1\n").unwrap();
+        assert_eq!(empty.row_clue(0), [] as [usize; 0]);
+        assert!(empty.encode().starts_with("rows: 0, 1\n"));
+    }
+
+    #[test]
+    fn rejects_clues_that_dont_fit() {
+        assert_eq!(
+            Nonogram::new(vec![vec![2, 1]], vec![vec![1], vec![], vec![1]]),
+            Err(BoardError::ClueTooLong {
+                clue: vec![2, 1],
+                length: 3
+            })
+        );
+        assert!(matches!(
+            Nonogram::decode("rows: 1\n"),
+            Err(BoardError::BadLine { line: 2, .. })
+        ));
+        assert!(matches!(
+            Nonogram::decode("rows: 1\ncols: 1\n#\n#\n"),
+            Err(BoardError::BadLine { line: 4, .. })
+        ));
+    }
+}
@@ -0,0 +1,347 @@
+use crate::SLIDING_SIZES;
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+const MAGIC: &[u8; 4] = b"SPDB";
+const VERSION: u8 = 1;
+/// Most states a single group may need while its table is built
+///
+/// A group of `k` tiles on `n` cells has `n^(k+1)` states counting the gap, so
+/// this allows five tiles on the 15 puzzle.
+const MAX_STATES: usize = 1 << 24;
+
+/// Precomputed lower bounds for a sliding-tile puzzle, one table per group of tiles
+///
+/// Each table holds, for every placement of its tiles, the fewest moves *of
+/// those tiles* needed to bring them home. Since no move is counted by two
+/// groups, the tables can be added up and still never overestimate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatternDb {
+    size: usize,
+    groups: Vec<Vec<u8>>,
+    tables: Vec<Vec<u8>>,
+}
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum PatternError {
+    #[error("A pattern database can't be built for a puzzle {0} across")]
+    BadSize(usize),
+    #[error("The groups must hold tiles 1 to {max} between them, each exactly once")]
+    BadGroups { max: usize },
+    #[error("A group of {tiles} tiles is too big to tabulate")]
+    GroupTooBig { tiles: usize },
+    #[error("Not a pattern database (bad magic number)")]
+    BadMagic,
+    #[error("Unsupported pattern database version {0}")]
+    UnsupportedVersion(u8),
+    #[error("The pattern database is truncated")]
+    Truncated,
+    #[error("Couldn't read the pattern database")]
+    Read(#[source] io::Error),
+    #[error("Couldn't open {path}")]
+    Open { path: PathBuf, source: io::Error },
+    #[error("Couldn't save the pattern database to {path}")]
+    Save { path: PathBuf, source: io::Error },
+}
+
+impl PatternDb {
+    /// A sensible way to split the tiles of a `size` x `size` puzzle into groups
+    pub fn standard_groups(size: usize) -> Vec<Vec<u8>> {
+        // 15 퍼즐은 흔히 쓰는 5-5-5 분할을 씁니다. 서로 붙어 있는 타일끼리 묶여야 값이 커집니다.
+        if size == 4 {
+            return vec![
+                vec![1, 2, 3, 4, 7],
+                vec![5, 6, 9, 10, 13],
+                vec![8, 11, 12, 14, 15],
+            ];
+        }
+        let cells = size * size;
+        let per_group = (1..cells)
+            .take_while(|&k| fits(cells, k))
+            .last()
+            .unwrap_or(1);
+        let tiles: Vec<u8> = (1..cells as u8).collect();
+        tiles.chunks(per_group).map(<[u8]>::to_vec).collect()
+    }
+
+    /// Tabulate every group by searching backwards from the solved puzzle
+    pub fn build(size: usize, groups: Vec<Vec<u8>>) -> Result<Self, PatternError> {
+        check(size, &groups)?;
+        let tables = groups
+            .iter()
+            .map(|group| build_table(size, group))
+            .collect();
+        Ok(Self {
+            size,
+            groups,
+            tables,
+        })
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn groups(&self) -> &[Vec<u8>] {
+        &self.groups
+    }
+
+    /// Which group `tile` is in, and its place value in that group's table index
+    pub(crate) fn place_of(&self, tile: u8) -> (usize, usize) {
+        let cells = self.size * self.size;
+        for (g, group) in self.groups.iter().enumerate() {
+            if let Some(i) = group.iter().position(|&t| t == tile) {
+                return (g, cells.pow(i as u32));
+            }
+        }
+        unreachable!("every tile is in a group")
+    }
+
+    /// Where each group's tiles are, as an index into its table
+    pub(crate) fn indexes(&self, tiles: &[u8]) -> Vec<usize> {
+        let cells = self.size * self.size;
+        let mut positions = vec![0; cells];
+        for (cell, &tile) in tiles.iter().enumerate() {
+            positions[usize::from(tile)] = cell;
+        }
+        self.groups
+            .iter()
+            .map(|group| index(cells, group.iter().map(|&t| positions[usize::from(t)])))
+            .collect()
+    }
+
+    /// The lower bound for the groups at `indexes`
+    pub(crate) fn estimate(&self, indexes: &[usize]) -> u32 {
+        self.tables
+            .iter()
+            .zip(indexes)
+            .map(|(table, &i)| u32::from(table[i]))
+            .sum()
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), PatternError> {
+        let path = path.as_ref();
+        let save = |source| PatternError::Save {
+            path: path.to_path_buf(),
+            source,
+        };
+        let mut out = BufWriter::new(File::create(path).map_err(save)?);
+        self.write_to(&mut out).map_err(save)?;
+        out.flush().map_err(save)
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, PatternError> {
+        let path = path.as_ref();
+        let fh = File::open(path).map_err(|source| PatternError::Open {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        Self::from_reader(BufReader::new(fh))
+    }
+
+    pub fn write_to<W: Write>(&self, mut out: W) -> io::Result<()> {
+        out.write_all(MAGIC)?;
+        out.write_all(&[VERSION, self.size as u8, self.groups.len() as u8])?;
+        for group in &self.groups {
+            out.write_all(&[group.len() as u8])?;
+            out.write_all(group)?;
+        }
+        for table in &self.tables {
+            out.write_all(table)?;
+        }
+        Ok(())
+    }
+
+    pub fn from_reader<R: Read>(mut reader: R) -> Result<Self, PatternError> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data).map_err(PatternError::Read)?;
+        let mut data = data.as_slice();
+        let mut take = |len: usize| -> Result<&[u8], PatternError> {
+            if data.len() < len {
+                return Err(PatternError::Truncated);
+            }
+            let (head, rest) = data.split_at(len);
+            data = rest;
+            Ok(head)
+        };
+
+        if take(4)? != MAGIC {
+            return Err(PatternError::BadMagic);
+        }
+        let &[version, size, count] = take(3)? else {
+            unreachable!()
+        };
+        if version != VERSION {
+            return Err(PatternError::UnsupportedVersion(version));
+        }
+        let size = usize::from(size);
+        let mut groups = Vec::new();
+        for _ in 0..count {
+            let len = usize::from(take(1)?[0]);
+            groups.push(take(len)?.to_vec());
+        }
+        // 표를 읽기 전에 그룹부터 검사해야 표 크기를 믿을 수 있습니다.
+        check(size, &groups)?;
+        let cells = size * size;
+        let tables = groups
+            .iter()
+            .map(|group| take(cells.pow(group.len() as u32)).map(<[u8]>::to_vec))
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            size,
+            groups,
+            tables,
+        })
+    }
+}
+
+/// Whether a group of `tiles` tiles on `cells` cells is small enough to tabulate
+fn fits(cells: usize, tiles: usize) -> bool {
+    cells
+        .checked_pow(tiles as u32 + 1)
+        .is_some_and(|states| states <= MAX_STATES)
+}
+
+fn check(size: usize, groups: &[Vec<u8>]) -> Result<(), PatternError> {
+    if !SLIDING_SIZES.contains(&size) {
+        return Err(PatternError::BadSize(size));
+    }
+    let cells = size * size;
+    if let Some(group) = groups.iter().find(|group| !fits(cells, group.len())) {
+        return Err(PatternError::GroupTooBig { tiles: group.len() });
+    }
+    let mut tiles: Vec<u8> = groups.iter().flatten().copied().collect();
+    tiles.sort_unstable();
+    if !tiles.iter().map(|&t| usize::from(t)).eq(1..cells) {
+        return Err(PatternError::BadGroups { max: cells - 1 });
+    }
+    Ok(())
+}
+
+/// Positions written as digits in base `cells`, the first one lowest
+fn index(cells: usize, positions: impl DoubleEndedIterator<Item = usize>) -> usize {
+    positions.rev().fold(0, |acc, p| acc * cells + p)
+}
+
+/// Breadth first search from the goal over the group's tiles and the gap
+///
+/// Sliding any other tile into the gap costs nothing, so this is a 0-1 BFS:
+/// free moves go to the front of the queue and paid ones to the back.
+fn build_table(size: usize, group: &[u8]) -> Vec<u8> {
+    let cells = size * size;
+    let mut table = vec![u8::MAX; cells.pow(group.len() as u32)];
+    let mut dist = vec![u8::MAX; table.len() * cells];
+    let mut done = vec![false; dist.len()];
+    let mut queue = VecDeque::new();
+
+    // 상태 번호 = 그룹 타일 위치로 만든 표 번호 * 칸 수 + 빈칸 위치
+    let goal: Vec<usize> = group.iter().map(|&t| usize::from(t) - 1).collect();
+    let start = index(cells, goal.iter().copied()) * cells + cells - 1;
+    dist[start] = 0;
+    queue.push_back(start);
+
+    let mut positions = vec![0; group.len()];
+    while let Some(state) = queue.pop_front() {
+        if std::mem::replace(&mut done[state], true) {
+            continue;
+        }
+        let d = dist[state];
+        let (pattern, blank) = (state / cells, state % cells);
+        // 0-1 BFS 는 거리 순서대로 꺼내니, 처음 꺼낸 거리가 이 배치의 최솟값입니다.
+        table[pattern] = table[pattern].min(d);
+
+        let mut rest = pattern;
+        for p in positions.iter_mut() {
+            *p = rest % cells;
+            rest /= cells;
+        }
+        let (row, col) = (blank / size, blank % size);
+        let neighbours = [
+            (row > 0).then(|| blank - size),
+            (row + 1 < size).then(|| blank + size),
+            (col > 0).then(|| blank - 1),
+            (col + 1 < size).then(|| blank + 1),
+        ];
+        for next in neighbours.into_iter().flatten() {
+            let (state, cost) = match positions.iter().position(|&p| p == next) {
+                Some(i) => {
+                    positions[i] = blank;
+                    let moved = index(cells, positions.iter().copied());
+                    positions[i] = next;
+                    (moved * cells + next, d.saturating_add(1))
+                }
+                None => (pattern * cells + next, d),
+            };
+            if cost < dist[state] {
+                dist[state] = cost;
+                if cost == d {
+                    queue.push_front(state);
+                } else {
+                    queue.push_back(state);
+                }
+            }
+        }
+    }
+    table
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn goal_costs_nothing_and_one_tile_costs_its_distance() {
+        let db = PatternDb::build(3, vec![vec![1], vec![2, 3, 4, 5, 6, 7, 8]]);
+        assert!(matches!(db, Err(PatternError::GroupTooBig { tiles: 7 })));
+
+        let db = PatternDb::build(3, vec![vec![1, 2, 3, 4], vec![5, 6, 7, 8]]).unwrap();
+        let solved = [1, 2, 3, 4, 5, 6, 7, 8, 0];
+        assert_eq!(db.estimate(&db.indexes(&solved)), 0);
+        // 1 과 8 을 바꾸면 1 은 4칸, 8 은 4칸 떨어져 있습니다.
+        let swapped = [8, 2, 3, 4, 5, 6, 7, 1, 0];
+        assert!(db.estimate(&db.indexes(&swapped)) >= 8);
+    }
+
+    #[test]
+    fn groups_must_cover_every_tile_once() {
+        assert!(matches!(
+            PatternDb::build(3, vec![vec![1, 2, 3], vec![3, 4, 5, 6, 7, 8]]),
+            Err(PatternError::BadGroups { max: 8 })
+        ));
+        assert!(matches!(
+            PatternDb::build(3, vec![vec![1, 2, 3, 4]]),
+            Err(PatternError::BadGroups { max: 8 })
+        ));
+        assert!(matches!(
+            PatternDb::build(1, vec![]),
+            Err(PatternError::BadSize(1))
+        ));
+        assert_eq!(PatternDb::standard_groups(4).len(), 3);
+        assert!(PatternDb::standard_groups(5)
+            .iter()
+            .all(|group| fits(25, group.len())));
+    }
+
+    #[test]
+    fn round_trips_through_bytes() {
+        let db = PatternDb::build(3, vec![vec![1, 2, 3, 4], vec![5, 6, 7, 8]]).unwrap();
+        let mut bytes = Vec::new();
+        db.write_to(&mut bytes).unwrap();
+        assert_eq!(bytes.len(), 4 + 3 + 5 + 5 + 2 * 9usize.pow(4));
+        assert_eq!(PatternDb::from_reader(bytes.as_slice()).unwrap(), db);
+
+        assert!(matches!(
+            PatternDb::from_reader(&bytes[..bytes.len() - 1]),
+            Err(PatternError::Truncated)
+        ));
+        bytes[0] = b'X';
+        assert!(matches!(
+            PatternDb::from_reader(bytes.as_slice()),
+            Err(PatternError::BadMagic)
+        ));
+    }
+}
@@ -0,0 +1,142 @@
+use crate::Puzzle;
+use std::collections::hash_set::{self, HashSet};
+
+/// A collection of puzzles without duplicates, where names that differ only in case count as the
+/// same puzzle
+#[derive(Debug, Clone, Default)]
+pub struct PuzzleSet {
+    puzzles: HashSet<Puzzle>,
+}
+
+impl PuzzleSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `puzzle` unless an equal one is already in the set. Returns whether it was added.
+    // 이미 있으면 먼저 들어온 쪽의 이름 표기를 그대로 둡니다.
+    pub fn insert(&mut self, puzzle: Puzzle) -> bool {
+        self.puzzles.insert(puzzle)
+    }
+
+    pub fn contains(&self, puzzle: &Puzzle) -> bool {
+        self.puzzles.contains(puzzle)
+    }
+
+    /// The stored puzzle equal to `puzzle`, spelled the way it was first added
+    pub fn get(&self, puzzle: &Puzzle) -> Option<&Puzzle> {
+        self.puzzles.get(puzzle)
+    }
+
+    pub fn remove(&mut self, puzzle: &Puzzle) -> Option<Puzzle> {
+        self.puzzles.take(puzzle)
+    }
+
+    pub fn len(&self) -> usize {
+        self.puzzles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.puzzles.is_empty()
+    }
+
+    /// The puzzles in no particular order
+    pub fn iter(&self) -> hash_set::Iter<'_, Puzzle> {
+        self.puzzles.iter()
+    }
+
+    /// The puzzles by name, ignoring case, and then by number of pieces
+    pub fn sorted(&self) -> Vec<&Puzzle> {
+        let mut puzzles: Vec<_> = self.puzzles.iter().collect();
+        puzzles.sort();
+        puzzles
+    }
+}
+
+impl FromIterator<Puzzle> for PuzzleSet {
+    fn from_iter<I: IntoIterator<Item = Puzzle>>(iter: I) -> Self {
+        Self {
+            puzzles: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl Extend<Puzzle> for PuzzleSet {
+    fn extend<I: IntoIterator<Item = Puzzle>>(&mut self, iter: I) {
+        self.puzzles.extend(iter);
+    }
+}
+
+impl IntoIterator for PuzzleSet {
+    type Item = Puzzle;
+    type IntoIter = hash_set::IntoIter<Puzzle>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.puzzles.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a PuzzleSet {
+    type Item = &'a Puzzle;
+    type IntoIter = hash_set::Iter<'a, Puzzle>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.puzzles.iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn puzzle(name: &str, num_pieces: u32) -> Puzzle {
+        Puzzle {
+            num_pieces,
+            name: name.to_string(),
+        }
+    }
+
+    #[test]
+    fn keeps_the_first_spelling() {
+        let mut set = PuzzleSet::new();
+        assert!(set.insert(puzzle("Forest Lake", 30)));
+        assert!(!set.insert(puzzle("FOREST LAKE", 30)));
+        assert!(set.insert(puzzle("forest lake", 500)));
+        assert_eq!(set.len(), 2);
+        assert_eq!(
+            set.get(&puzzle("forest LAKE", 30)).unwrap().name,
+            "Forest Lake"
+        );
+    }
+
+    #[test]
+    fn removes_whatever_the_case() {
+        let mut set: PuzzleSet = [puzzle("Lake", 10), puzzle("City", 10)]
+            .into_iter()
+            .collect();
+        assert_eq!(set.remove(&puzzle("lake", 10)).unwrap().name, "Lake");
+        assert!(!set.contains(&puzzle("LAKE", 10)));
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn sorts_by_name_then_pieces() {
+        let set: PuzzleSet = [
+            puzzle("lake", 50),
+            puzzle("City", 10),
+            puzzle("Lake", 20),
+            puzzle("bay", 99),
+        ]
+        .into_iter()
+        .collect();
+        let sorted: Vec<_> = set
+            .sorted()
+            .into_iter()
+            .map(|p| (p.name.as_str(), p.num_pieces))
+            .collect();
+        assert_eq!(
+            sorted,
+            [("bay", 99), ("City", 10), ("Lake", 20), ("lake", 50)]
+        );
+    }
+}
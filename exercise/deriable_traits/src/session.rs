@@ -0,0 +1,101 @@
+use crate::{BoardError, LoadError, MoveError, PuzzleKind, PuzzleType};
+use std::io::{self, Read, Write};
+
+/// A game of any puzzle kind, with undo and save files
+#[derive(Debug, Clone)]
+pub struct Session<K: PuzzleKind> {
+    puzzle: K,
+    /// Moves that take back what was played, newest last
+    undo: Vec<K::Move>,
+}
+
+impl<K: PuzzleKind> Session<K> {
+    pub fn new(puzzle: K) -> Self {
+        Self {
+            puzzle,
+            undo: Vec::new(),
+        }
+    }
+
+    pub fn puzzle(&self) -> &K {
+        &self.puzzle
+    }
+
+    pub fn into_puzzle(self) -> K {
+        self.puzzle
+    }
+
+    /// Make a move. A move the rules don't allow leaves the game untouched.
+    pub fn play(&mut self, mv: K::Move) -> Result<(), MoveError> {
+        let back = self.puzzle.apply(mv)?;
+        self.undo.push(back);
+        Ok(())
+    }
+
+    /// Take back the last move. Returns false if there is nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        let Some(back) = self.undo.pop() else {
+            return false;
+        };
+        // 되돌리는 수는 방금 전까지 있던 상태로 가는 수라서 규칙에 걸릴 수가 없습니다.
+        self.puzzle
+            .apply(back)
+            .expect("taking back a move is always allowed");
+        true
+    }
+
+    /// How many moves can be taken back
+    pub fn moves(&self) -> usize {
+        self.undo.len()
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.puzzle.is_solved()
+    }
+
+    /// Write the kind on the first line and the puzzle's state after it
+    ///
+    /// The undo history is not saved; a loaded game starts with none.
+    pub fn save<W: Write>(&self, mut out: W) -> io::Result<()> {
+        writeln!(out, "{}", K::TYPE)?;
+        out.write_all(self.puzzle.encode().as_bytes())
+    }
+
+    pub fn load<R: Read>(mut input: R) -> Result<Self, LoadError> {
+        let mut text = String::new();
+        input.read_to_string(&mut text).map_err(LoadError::Read)?;
+        let found = saved_type(&text)?;
+        if found != K::TYPE {
+            return Err(LoadError::WrongType {
+                expected: K::TYPE,
+                found,
+            });
+        }
+        let body = text.split_once('\n').map_or("", |(_, body)| body);
+        // 첫 줄은 종류라서, 줄 번호를 하나씩 밀어야 파일의 줄과 맞습니다.
+        let puzzle = K::decode(body).map_err(|e| match e {
+            BoardError::BadLine { line, reason } => BoardError::BadLine {
+                line: line + 1,
+                reason,
+            },
+            e => e,
+        })?;
+        Ok(Self::new(puzzle))
+    }
+}
+
+/// Which kind of puzzle a saved game holds, so the caller knows which `Session` to load it into
+pub fn saved_type(text: &str) -> Result<PuzzleType, LoadError> {
+    let first = text.lines().next().map(str::trim).unwrap_or_default();
+    if first.is_empty() {
+        return Err(LoadError::MissingType);
+    }
+    let found = first
+        .parse()
+        .map_err(|_| LoadError::UnknownType(first.to_string()))?;
+    // 조각 맞추기(jigsaw)는 PuzzleKind 가 아니라서 저장할 수도 불러올 수도 없습니다.
+    if found == PuzzleType::Jigsaw {
+        return Err(LoadError::NotSaveable(found));
+    }
+    Ok(found)
+}
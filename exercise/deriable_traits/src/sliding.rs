@@ -0,0 +1,248 @@
+use crate::{BoardError, MoveError, PuzzleKind, PuzzleType};
+use std::fmt;
+
+/// Smallest and largest sliding puzzles, counted in tiles across
+pub const SLIDING_SIZES: std::ops::RangeInclusive<usize> = 2..=15;
+
+/// Which way a tile next to the gap slides into it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Slide {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Slide {
+    pub const ALL: [Slide; 4] = [Slide::Up, Slide::Down, Slide::Left, Slide::Right];
+
+    pub fn opposite(self) -> Slide {
+        match self {
+            Slide::Up => Slide::Down,
+            Slide::Down => Slide::Up,
+            Slide::Left => Slide::Right,
+            Slide::Right => Slide::Left,
+        }
+    }
+}
+
+/// The 15 puzzle and its smaller and bigger cousins
+///
+/// Tiles are numbered from 1, and 0 is the gap. The puzzle is solved when the
+/// tiles are in order, row by row, with the gap in the bottom right corner.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SlidingTile {
+    size: usize,
+    tiles: Vec<u8>,
+    blank: usize,
+}
+
+impl SlidingTile {
+    /// A solved `size` x `size` puzzle
+    pub fn new(size: usize) -> Result<Self, BoardError> {
+        // 크기를 먼저 봐야 타일 번호가 u8 을 넘어 엉뚱한 판이 되지 않습니다.
+        check_size(size)?;
+        let mut tiles: Vec<u8> = (1..(size * size) as u8).collect();
+        tiles.push(0);
+        Self::from_tiles(size, tiles)
+    }
+
+    /// A puzzle with `tiles` laid out row by row
+    pub fn from_tiles(size: usize, tiles: Vec<u8>) -> Result<Self, BoardError> {
+        check_size(size)?;
+        let mut sorted = tiles.clone();
+        sorted.sort_unstable();
+        let all_once = sorted.len() == size * size
+            && sorted
+                .iter()
+                .enumerate()
+                .all(|(i, &tile)| usize::from(tile) == i);
+        if !all_once {
+            return Err(BoardError::BadTiles {
+                max: size * size - 1,
+            });
+        }
+        let blank = tiles.iter().position(|&tile| tile == 0).unwrap();
+        Ok(Self { size, tiles, blank })
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// The tiles row by row, with 0 for the gap
+    pub fn tiles(&self) -> &[u8] {
+        &self.tiles
+    }
+
+    /// Where the gap is, counted row by row
+    pub fn blank(&self) -> usize {
+        self.blank
+    }
+
+    /// The slides that are possible right now
+    pub fn slides(&self) -> impl Iterator<Item = Slide> + '_ {
+        Slide::ALL
+            .into_iter()
+            .filter(|&slide| self.source(slide).is_some())
+    }
+
+    /// The tile that would slide into the gap
+    fn source(&self, slide: Slide) -> Option<usize> {
+        let (row, col) = (self.blank / self.size, self.blank % self.size);
+        // 위로 미는 타일은 빈칸 아래에 있는 타일입니다.
+        match slide {
+            Slide::Up if row + 1 < self.size => Some(self.blank + self.size),
+            Slide::Down if row > 0 => Some(self.blank - self.size),
+            Slide::Left if col + 1 < self.size => Some(self.blank + 1),
+            Slide::Right if col > 0 => Some(self.blank - 1),
+            _ => None,
+        }
+    }
+}
+
+fn check_size(size: usize) -> Result<(), BoardError> {
+    if !SLIDING_SIZES.contains(&size) {
+        return Err(BoardError::BadSize {
+            kind: PuzzleType::SlidingTile,
+            size,
+        });
+    }
+    Ok(())
+}
+
+impl PuzzleKind for SlidingTile {
+    type Move = Slide;
+
+    const TYPE: PuzzleType = PuzzleType::SlidingTile;
+
+    fn apply(&mut self, slide: Slide) -> Result<Slide, MoveError> {
+        let from = self.source(slide).ok_or(MoveError::NoTile)?;
+        self.tiles.swap(from, self.blank);
+        self.blank = from;
+        Ok(slide.opposite())
+    }
+
+    fn is_solved(&self) -> bool {
+        let last = self.tiles.len() - 1;
+        self.tiles[last] == 0
+            && self.tiles[..last]
+                .iter()
+                .enumerate()
+                .all(|(i, &tile)| usize::from(tile) == i + 1)
+    }
+
+    fn encode(&self) -> String {
+        self.to_string()
+    }
+
+    fn decode(text: &str) -> Result<Self, BoardError> {
+        let mut tiles = Vec::new();
+        let mut size = 0;
+        for (i, line) in text.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let row = line
+                .split_whitespace()
+                .map(|word| match word {
+                    "." => Ok(0),
+                    _ => word.parse().map_err(|_| BoardError::BadLine {
+                        line: i + 1,
+                        reason: format!("{:?} is not a tile", word),
+                    }),
+                })
+                .collect::<Result<Vec<u8>, _>>()?;
+            // 첫 줄의 칸 수가 퍼즐의 크기입니다. 나머지 줄도 같아야 하죠.
+            if size == 0 {
+                size = row.len();
+            } else if row.len() != size {
+                return Err(BoardError::BadLine {
+                    line: i + 1,
+                    reason: format!("expected {} tiles, found {}", size, row.len()),
+                });
+            }
+            tiles.extend(row);
+        }
+        Self::from_tiles(size, tiles)
+    }
+}
+
+impl fmt::Display for SlidingTile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let width = (self.tiles.len() - 1).to_string().len();
+        for row in self.tiles.chunks(self.size) {
+            let cells: Vec<_> = row
+                .iter()
+                .map(|&tile| match tile {
+                    0 => format!("{:>width$}", "."),
+                    _ => format!("{:>width$}", tile),
+                })
+                .collect();
+            writeln!(f, "{}", cells.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn slides_and_slides_back() {
+        let mut puzzle = SlidingTile::new(3).unwrap();
+        assert!(puzzle.is_solved());
+        assert_eq!(
+            puzzle.slides().collect::<Vec<_>>(),
+            [Slide::Down, Slide::Right]
+        );
+        assert_eq!(puzzle.apply(Slide::Up), Err(MoveError::NoTile));
+
+        let back = puzzle.apply(Slide::Right).unwrap();
+        assert_eq!(puzzle.tiles(), [1, 2, 3, 4, 5, 6, 7, 0, 8]);
+        assert!(!puzzle.is_solved());
+        puzzle.apply(back).unwrap();
+        assert!(puzzle.is_solved());
+    }
+
+    #[test]
+    fn round_trips_through_text() {
+        let puzzle = SlidingTile::from_tiles(4, (0..16).rev().collect()).unwrap();
+        let text = puzzle.encode();
+        assert!(text.starts_with("15 14 13 12\n"));
+        assert!(text.ends_with(" 3  2  1  .\n"));
+        assert_eq!(SlidingTile::decode(&text).unwrap(), puzzle);
+    }
+
+    #[test]
+    fn rejects_bad_layouts() {
+        assert_eq!(
+            SlidingTile::from_tiles(2, vec![1, 1, 2, 0]),
+            Err(BoardError::BadTiles { max: 3 })
+        );
+        assert_eq!(
+            SlidingTile::from_tiles(2, vec![1, 9, 2, 0]),
+            Err(BoardError::BadTiles { max: 3 })
+        );
+        assert!(matches!(
+            SlidingTile::new(1),
+            Err(BoardError::BadSize { size: 1, .. })
+        ));
+        // 16 x 16 부터는 타일 번호가 u8 에 들어가지 않습니다.
+        for size in [16, 100] {
+            assert!(matches!(
+                SlidingTile::new(size),
+                Err(BoardError::BadSize { size: s, .. }) if s == size
+            ));
+        }
+        assert!(matches!(
+            SlidingTile::decode("1 2\n3 . 4\n"),
+            Err(BoardError::BadLine { line: 2, .. })
+        ));
+        assert!(matches!(
+            SlidingTile::decode("1 x\n3 .\n"),
+            Err(BoardError::BadLine { line: 1, .. })
+        ));
+    }
+}
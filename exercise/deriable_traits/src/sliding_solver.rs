@@ -0,0 +1,355 @@
+use crate::{PatternDb, PuzzleKind, Slide, SlidingTile};
+use thiserror::Error;
+
+/// How much work the search did
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SearchStats {
+    /// Positions expanded, over all iterations
+    pub nodes: u64,
+    /// Times the search started over with a higher bound
+    pub iterations: u32,
+}
+
+/// The fewest slides that solve a puzzle and what it cost to find them
+#[derive(Debug, Clone)]
+pub struct SlideSolution {
+    pub slides: Vec<Slide>,
+    pub stats: SearchStats,
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SolveError {
+    #[error("No sequence of slides can solve this layout")]
+    Unsolvable,
+    #[error("The pattern database is for a puzzle {patterns} across, not {puzzle}")]
+    WrongPatterns { puzzle: usize, patterns: usize },
+    #[error("Gave up after expanding {nodes} positions")]
+    GaveUp { nodes: u64 },
+}
+
+/// Optimal solver for sliding-tile puzzles using IDA*
+///
+/// Without a pattern database it uses Manhattan distance plus linear
+/// conflicts, which is enough for the 8 puzzle and easy 15 puzzles. Hard 15
+/// puzzles need a [`PatternDb`].
+///
+/// ```no_run
+/// use deriable_traits::{PatternDb, SlidingSolver, SlidingTile, PuzzleKind};
+///
+/// let patterns = PatternDb::load("fifteen.pdb")?;
+/// let puzzle = SlidingTile::decode("14 13 15 7\n11 12 9 5\n6 . 2 1\n4 8 10 3\n")?;
+/// let solution = SlidingSolver::new().patterns(&patterns).solve(&puzzle)?;
+/// println!("{} slides", solution.slides.len());
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SlidingSolver<'a> {
+    patterns: Option<&'a PatternDb>,
+    max_nodes: Option<u64>,
+}
+
+impl<'a> SlidingSolver<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use `patterns` as well as Manhattan distance and linear conflicts, whichever says more
+    pub fn patterns(mut self, patterns: &'a PatternDb) -> Self {
+        self.patterns = Some(patterns);
+        self
+    }
+
+    /// Give up after expanding this many positions
+    pub fn max_nodes(mut self, nodes: u64) -> Self {
+        self.max_nodes = Some(nodes);
+        self
+    }
+
+    pub fn solve(&self, puzzle: &SlidingTile) -> Result<SlideSolution, SolveError> {
+        if let Some(patterns) = self.patterns {
+            if patterns.size() != puzzle.size() {
+                return Err(SolveError::WrongPatterns {
+                    puzzle: puzzle.size(),
+                    patterns: patterns.size(),
+                });
+            }
+        }
+        // 풀 수 없는 배치는 IDA* 가 끝없이 한계를 올리니 먼저 걸러야 합니다.
+        if !puzzle.is_solvable() {
+            return Err(SolveError::Unsolvable);
+        }
+        let mut search = Search::new(puzzle.clone(), self.patterns, self.max_nodes);
+        let mut bound = search.estimate();
+        loop {
+            search.stats.iterations += 1;
+            match search.dfs(0, bound, None)? {
+                Step::Found => {
+                    return Ok(SlideSolution {
+                        slides: search.path,
+                        stats: search.stats,
+                    })
+                }
+                Step::Exceeded(next) => bound = next,
+            }
+        }
+    }
+}
+
+impl SlidingTile {
+    /// Whether the tiles can be slid into order at all
+    ///
+    /// Every slide keeps the parity of the permutation, counted together with
+    /// the gap's row when the puzzle is an even number of tiles across, so
+    /// exactly half of all layouts can be solved.
+    pub fn is_solvable(&self) -> bool {
+        let tiles: Vec<u8> = self.tiles().iter().copied().filter(|&t| t != 0).collect();
+        let inversions = tiles
+            .iter()
+            .enumerate()
+            .map(|(i, a)| tiles[i + 1..].iter().filter(|&b| b < a).count())
+            .sum::<usize>();
+        let size = self.size();
+        if size % 2 == 1 {
+            inversions.is_multiple_of(2)
+        } else {
+            // 빈칸이 위아래로 움직이면 size - 1 개의 타일을 건너뛰어 반전 수의 홀짝이 바뀝니다.
+            let rows_from_bottom = size - 1 - self.blank() / size;
+            (inversions + rows_from_bottom).is_multiple_of(2)
+        }
+    }
+
+    /// Solve with Manhattan distance and linear conflicts
+    pub fn solve(&self) -> Result<SlideSolution, SolveError> {
+        SlidingSolver::new().solve(self)
+    }
+}
+
+enum Step {
+    Found,
+    /// The smallest estimate that went over the bound
+    Exceeded(u32),
+}
+
+struct Search<'a> {
+    puzzle: SlidingTile,
+    manhattan: u32,
+    /// Linear conflicts of each row, then of each column
+    conflicts: Vec<u32>,
+    patterns: Option<(&'a PatternDb, Vec<usize>)>,
+    path: Vec<Slide>,
+    stats: SearchStats,
+    max_nodes: Option<u64>,
+}
+
+impl<'a> Search<'a> {
+    fn new(puzzle: SlidingTile, patterns: Option<&'a PatternDb>, max_nodes: Option<u64>) -> Self {
+        let size = puzzle.size();
+        let manhattan = puzzle
+            .tiles()
+            .iter()
+            .enumerate()
+            .map(|(cell, &tile)| distance(size, cell, tile))
+            .sum();
+        let conflicts = (0..2 * size)
+            .map(|line| line_conflicts(&puzzle, line))
+            .collect();
+        let patterns = patterns.map(|db| (db, db.indexes(puzzle.tiles())));
+        Self {
+            puzzle,
+            manhattan,
+            conflicts,
+            patterns,
+            path: Vec::new(),
+            stats: SearchStats::default(),
+            max_nodes,
+        }
+    }
+
+    /// A lower bound on the slides still needed, zero only when solved
+    fn estimate(&self) -> u32 {
+        let classic = self.manhattan + self.conflicts.iter().sum::<u32>();
+        match &self.patterns {
+            Some((db, indexes)) => classic.max(db.estimate(indexes)),
+            None => classic,
+        }
+    }
+
+    /// Slide and bring every part of the estimate up to date
+    fn slide(&mut self, slide: Slide) -> bool {
+        let size = self.puzzle.size();
+        let to = self.puzzle.blank();
+        if self.puzzle.apply(slide).is_err() {
+            return false;
+        }
+        let from = self.puzzle.blank();
+        let tile = self.puzzle.tiles()[to];
+
+        self.manhattan = self.manhattan + distance(size, to, tile) - distance(size, from, tile);
+        // 위아래로 움직이면 두 줄(row)의 순서가, 옆으로 움직이면 두 열의 순서가 바뀝니다.
+        let lines = if from % size == to % size {
+            [from / size, to / size]
+        } else {
+            [size + from % size, size + to % size]
+        };
+        for line in lines {
+            self.conflicts[line] = line_conflicts(&self.puzzle, line);
+        }
+        if let Some((db, indexes)) = &mut self.patterns {
+            let (group, place) = db.place_of(tile);
+            indexes[group] = indexes[group] - from * place + to * place;
+        }
+        true
+    }
+
+    fn dfs(&mut self, depth: u32, bound: u32, last: Option<Slide>) -> Result<Step, SolveError> {
+        let estimate = self.estimate();
+        if depth + estimate > bound {
+            return Ok(Step::Exceeded(depth + estimate));
+        }
+        if estimate == 0 {
+            return Ok(Step::Found);
+        }
+        self.stats.nodes += 1;
+        if self.max_nodes.is_some_and(|max| self.stats.nodes > max) {
+            return Err(SolveError::GaveUp {
+                nodes: self.stats.nodes - 1,
+            });
+        }
+
+        let mut next = u32::MAX;
+        for slide in Slide::ALL {
+            // 방금 한 수를 되돌리는 수는 볼 필요가 없습니다.
+            if last == Some(slide.opposite()) || !self.slide(slide) {
+                continue;
+            }
+            self.path.push(slide);
+            match self.dfs(depth + 1, bound, Some(slide))? {
+                Step::Found => return Ok(Step::Found),
+                Step::Exceeded(f) => next = next.min(f),
+            }
+            self.path.pop();
+            self.slide(slide.opposite());
+        }
+        Ok(Step::Exceeded(next))
+    }
+}
+
+/// How far `tile` at `cell` is from home, counting rows and columns
+fn distance(size: usize, cell: usize, tile: u8) -> u32 {
+    if tile == 0 {
+        return 0;
+    }
+    let home = usize::from(tile) - 1;
+    let rows = (cell / size).abs_diff(home / size);
+    let cols = (cell % size).abs_diff(home % size);
+    (rows + cols) as u32
+}
+
+/// Extra moves forced by tiles that are in their home row (or column) but in the wrong order
+///
+/// Lines `0..size` are rows and `size..2 * size` are columns. Every tile that
+/// has to step out of the line to let the others pass costs two moves. The
+/// fewest tiles that must step out are the ones not on the longest run that is
+/// already in order.
+fn line_conflicts(puzzle: &SlidingTile, line: usize) -> u32 {
+    let size = puzzle.size();
+    let tiles = puzzle.tiles();
+    let homes: Vec<usize> = (0..size)
+        .map(|i| {
+            if line < size {
+                line * size + i
+            } else {
+                i * size + (line - size)
+            }
+        })
+        .filter_map(|cell| {
+            let tile = tiles[cell];
+            let home = usize::from(tile).checked_sub(1)?;
+            let same = if line < size {
+                home / size == line
+            } else {
+                home % size == line - size
+            };
+            same.then_some(home)
+        })
+        .collect();
+    if homes.len() < 2 {
+        return 0;
+    }
+    2 * (homes.len() - longest_increasing(&homes)) as u32
+}
+
+fn longest_increasing(values: &[usize]) -> usize {
+    // 줄 길이가 15 를 넘지 않으니 O(n²) 로 충분합니다.
+    let mut longest = vec![1; values.len()];
+    for i in 0..values.len() {
+        for j in 0..i {
+            if values[j] < values[i] {
+                longest[i] = longest[i].max(longest[j] + 1);
+            }
+        }
+    }
+    longest.into_iter().max().unwrap_or(0)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn puzzle(text: &str) -> SlidingTile {
+        SlidingTile::decode(text).unwrap()
+    }
+
+    #[test]
+    fn parity_decides_solvability() {
+        assert!(puzzle("1 2 3\n4 5 6\n7 8 .\n").is_solvable());
+        assert!(!puzzle("1 2 3\n4 5 6\n8 7 .\n").is_solvable());
+        assert!(puzzle("1 2\n. 3\n").is_solvable());
+        assert!(!puzzle("2 1\n3 .\n").is_solvable());
+        // 빈칸이 한 줄 올라가면 짝수 크기에서는 반전 수의 홀짝이 바뀌어야 합니다.
+        assert!(puzzle("1 2 3 4\n5 6 7 8\n9 10 11 .\n13 14 15 12\n").is_solvable());
+        assert!(!puzzle("1 2 3 4\n5 6 7 8\n9 10 11 12\n13 15 14 .\n").is_solvable());
+        assert_eq!(
+            puzzle("1 2 3\n4 5 6\n8 7 .\n").solve().unwrap_err(),
+            SolveError::Unsolvable
+        );
+    }
+
+    #[test]
+    fn linear_conflicts_count_pairs_in_the_wrong_order() {
+        let swapped = puzzle("2 1 3\n4 5 6\n7 8 .\n");
+        assert_eq!(line_conflicts(&swapped, 0), 2);
+        assert_eq!(line_conflicts(&swapped, 3), 0);
+        let reversed = puzzle("3 2 1\n4 5 6\n7 8 .\n");
+        assert_eq!(line_conflicts(&reversed, 0), 4);
+        let search = Search::new(swapped, None, None);
+        assert_eq!(search.estimate(), 2 + 2);
+    }
+
+    #[test]
+    fn solves_the_eight_puzzle_optimally() {
+        let start = puzzle("1 2 3\n4 . 6\n7 5 8\n");
+        let solution = start.solve().unwrap();
+        assert_eq!(solution.slides, [Slide::Up, Slide::Left]);
+
+        assert!(SlidingTile::new(3)
+            .unwrap()
+            .solve()
+            .unwrap()
+            .slides
+            .is_empty());
+    }
+
+    #[test]
+    fn gives_up_when_told() {
+        let hard = puzzle("8 6 7\n2 5 4\n3 . 1\n");
+        assert_eq!(
+            SlidingSolver::new()
+                .max_nodes(100)
+                .solve(&hard)
+                .unwrap_err(),
+            SolveError::GaveUp { nodes: 100 }
+        );
+    }
+}
@@ -0,0 +1,308 @@
+use crate::{BoardError, MoveError, PuzzleKind, PuzzleType};
+use std::fmt;
+
+/// Write `digit` into a cell, or clear it with `None`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fill {
+    pub row: usize,
+    pub col: usize,
+    pub digit: Option<u8>,
+}
+
+/// A 9 x 9 sudoku
+///
+/// The givens can't be changed. A digit may only go where the same digit isn't
+/// already in its row, column or 3 x 3 box, so a full grid is a solved one.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Sudoku {
+    /// Row by row, with 0 for an empty cell
+    cells: [u8; 81],
+    given: [bool; 81],
+}
+
+impl Sudoku {
+    /// A sudoku whose givens are `cells`, row by row, with 0 for an empty cell
+    pub fn new(cells: [u8; 81]) -> Result<Self, BoardError> {
+        let mut sudoku = Sudoku {
+            cells: [0; 81],
+            given: [false; 81],
+        };
+        for (i, &digit) in cells.iter().enumerate() {
+            if digit == 0 {
+                continue;
+            }
+            match sudoku.apply(Fill {
+                row: i / 9,
+                col: i % 9,
+                digit: Some(digit),
+            }) {
+                Ok(_) => sudoku.given[i] = true,
+                Err(MoveError::Conflict { digit }) => {
+                    return Err(BoardError::ConflictingGivens { digit })
+                }
+                Err(e) => {
+                    return Err(BoardError::BadLine {
+                        line: i / 9 + 1,
+                        reason: e.to_string(),
+                    })
+                }
+            }
+        }
+        Ok(sudoku)
+    }
+
+    /// The digit at `row`, `col`, if there is one
+    pub fn get(&self, row: usize, col: usize) -> Option<u8> {
+        match self.cells[index(row, col)?] {
+            0 => None,
+            digit => Some(digit),
+        }
+    }
+
+    pub fn is_given(&self, row: usize, col: usize) -> bool {
+        index(row, col).is_some_and(|i| self.given[i])
+    }
+
+    /// Whether `digit` is already somewhere that `row`, `col` can see, apart from that cell itself
+    fn conflicts(&self, row: usize, col: usize, digit: u8) -> bool {
+        let (box_row, box_col) = (row / 3 * 3, col / 3 * 3);
+        (0..9).any(|i| {
+            let peers = [(row, i), (i, col), (box_row + i / 3, box_col + i % 3)];
+            peers
+                .into_iter()
+                .any(|(r, c)| (r, c) != (row, col) && self.cells[r * 9 + c] == digit)
+        })
+    }
+
+    fn write_grid(&self, f: &mut fmt::Formatter, keep: impl Fn(usize) -> bool) -> fmt::Result {
+        for row in 0..9 {
+            for col in 0..9 {
+                let i = row * 9 + col;
+                match self.cells[i] {
+                    digit if digit != 0 && keep(i) => write!(f, "{}", digit)?,
+                    _ => write!(f, ".")?,
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+fn index(row: usize, col: usize) -> Option<usize> {
+    (row < 9 && col < 9).then_some(row * 9 + col)
+}
+
+impl PuzzleKind for Sudoku {
+    type Move = Fill;
+
+    const TYPE: PuzzleType = PuzzleType::Sudoku;
+
+    fn apply(&mut self, fill: Fill) -> Result<Fill, MoveError> {
+        let Fill { row, col, digit } = fill;
+        let i = index(row, col).ok_or(MoveError::OffBoard { row, col })?;
+        if self.given[i] {
+            return Err(MoveError::Given { row, col });
+        }
+        if let Some(digit) = digit {
+            if !(1..=9).contains(&digit) {
+                return Err(MoveError::BadDigit(digit));
+            }
+            if self.conflicts(row, col, digit) {
+                return Err(MoveError::Conflict { digit });
+            }
+        }
+        let before = self.get(row, col);
+        self.cells[i] = digit.unwrap_or(0);
+        Ok(Fill {
+            digit: before,
+            ..fill
+        })
+    }
+
+    fn is_solved(&self) -> bool {
+        // 숫자를 넣을 때마다 규칙을 검사하니, 다 채워졌으면 풀린 겁니다.
+        self.cells.iter().all(|&digit| digit != 0)
+    }
+
+    /// The givens, a blank line, and then what the player has written
+    fn encode(&self) -> String {
+        self.to_string()
+    }
+
+    fn decode(text: &str) -> Result<Self, BoardError> {
+        let mut grids = [[0; 81], [0; 81]];
+        let mut rows = 0;
+        let mut row_lines = Vec::new();
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let bad = |reason: String| BoardError::BadLine {
+                line: i + 1,
+                reason,
+            };
+            if rows == 18 {
+                return Err(bad("too many rows".to_string()));
+            }
+            if line.chars().count() != 9 {
+                return Err(bad(format!(
+                    "expected 9 cells, found {}",
+                    line.chars().count()
+                )));
+            }
+            for (col, c) in line.chars().enumerate() {
+                grids[rows / 9][rows % 9 * 9 + col] = match c {
+                    '.' => 0,
+                    '1'..='9' => c as u8 - b'0',
+                    _ => return Err(bad(format!("{:?} is not a digit", c))),
+                };
+            }
+            row_lines.push(i + 1);
+            rows += 1;
+        }
+        // 두 번째 판은 없어도 됩니다. 아직 아무것도 쓰지 않은 게임이죠.
+        if rows != 9 && rows != 18 {
+            return Err(BoardError::BadLine {
+                line: text.lines().count(),
+                reason: format!("expected 9 or 18 rows, found {}", rows),
+            });
+        }
+        let [givens, entries] = grids;
+        let mut sudoku = Sudoku::new(givens)?;
+        for (i, &digit) in entries.iter().enumerate().filter(|(_, &d)| d != 0) {
+            sudoku
+                .apply(Fill {
+                    row: i / 9,
+                    col: i % 9,
+                    digit: Some(digit),
+                })
+                .map_err(|e| BoardError::BadLine {
+                    line: row_lines[9 + i / 9],
+                    reason: e.to_string(),
+                })?;
+        }
+        Ok(sudoku)
+    }
+}
+
+impl fmt::Display for Sudoku {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write_grid(f, |i| self.given[i])?;
+        writeln!(f)?;
+        self.write_grid(f, |i| !self.given[i])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const GIVENS: &str = "\
+53..7....
+6..195...
+.98....6.
+8...6...3
+4..8.3..1
+7...2...6
+.6....28.
+...419..5
+....8..79
+";
+
+    const SOLUTION: &str = "\
+534678912
+672195348
+198342567
+859761423
+426853791
+713924856
+961537284
+287419635
+345286179
+";
+
+    fn fills() -> Vec<Fill> {
+        let givens = Sudoku::decode(GIVENS).unwrap();
+        SOLUTION
+            .lines()
+            .enumerate()
+            .flat_map(|(row, line)| {
+                line.bytes().enumerate().map(move |(col, b)| Fill {
+                    row,
+                    col,
+                    digit: Some(b - b'0'),
+                })
+            })
+            .filter(|fill| !givens.is_given(fill.row, fill.col))
+            .collect()
+    }
+
+    #[test]
+    fn fills_in_the_solution() {
+        let mut sudoku = Sudoku::decode(GIVENS).unwrap();
+        let fills = fills();
+        assert_eq!(fills.len(), 81 - 30);
+        for fill in fills {
+            assert!(!sudoku.is_solved());
+            sudoku.apply(fill).unwrap();
+        }
+        assert!(sudoku.is_solved());
+    }
+
+    #[test]
+    fn checks_the_rules() {
+        let mut sudoku = Sudoku::decode(GIVENS).unwrap();
+        let at = |row, col, digit| Fill { row, col, digit };
+        assert_eq!(
+            sudoku.apply(at(0, 0, Some(1))),
+            Err(MoveError::Given { row: 0, col: 0 })
+        );
+        assert_eq!(
+            sudoku.apply(at(0, 2, Some(5))),
+            Err(MoveError::Conflict { digit: 5 })
+        );
+        // 같은 상자에 있는 9
+        assert_eq!(
+            sudoku.apply(at(0, 2, Some(9))),
+            Err(MoveError::Conflict { digit: 9 })
+        );
+        assert_eq!(sudoku.apply(at(0, 2, Some(0))), Err(MoveError::BadDigit(0)));
+        assert_eq!(
+            sudoku.apply(at(9, 2, Some(4))),
+            Err(MoveError::OffBoard { row: 9, col: 2 })
+        );
+
+        let back = sudoku.apply(at(0, 2, Some(4))).unwrap();
+        assert_eq!(back, at(0, 2, None));
+        assert_eq!(sudoku.apply(at(0, 2, Some(2))).unwrap(), at(0, 2, Some(4)));
+    }
+
+    #[test]
+    fn saves_givens_and_entries_apart() {
+        let mut sudoku = Sudoku::decode(GIVENS).unwrap();
+        for fill in fills().into_iter().take(3) {
+            sudoku.apply(fill).unwrap();
+        }
+        let text = sudoku.encode();
+        assert!(text.starts_with(GIVENS));
+        assert!(text.contains("\n\n..46.8...\n.........\n"));
+        let loaded = Sudoku::decode(&text).unwrap();
+        assert_eq!(loaded, sudoku);
+        assert!(!loaded.is_given(0, 2));
+    }
+
+    #[test]
+    fn rejects_conflicting_givens() {
+        let text = GIVENS.replacen("53..7", "53.37", 1);
+        assert_eq!(
+            Sudoku::decode(&text),
+            Err(BoardError::ConflictingGivens { digit: 3 })
+        );
+        assert!(matches!(
+            Sudoku::decode("53..7....\n"),
+            Err(BoardError::BadLine { .. })
+        ));
+    }
+}
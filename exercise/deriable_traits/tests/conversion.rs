@@ -0,0 +1,37 @@
+use deriable_traits::Puzzle;
+use puzzles::{Profile, PuzzleError};
+
+#[test]
+fn converts_to_and_from_the_canonical_puzzle() {
+    let canonical = puzzles::Puzzle::try_from(Profile::forest_lake()).unwrap();
+    let light = Puzzle::from(&canonical);
+    assert_eq!(light.name, "Forest Lake");
+    assert_eq!(light.num_pieces, canonical.num_pieces);
+
+    let back = puzzles::Puzzle::try_from(&light).unwrap();
+    assert_eq!(Profile::from(&back), Profile::forest_lake());
+    assert_eq!(Puzzle::from(&back), light);
+}
+
+#[test]
+fn keeps_its_own_default() {
+    let puzzle = Puzzle::default();
+    assert_eq!(puzzle.num_pieces, 30);
+    assert_eq!(puzzle.name, "Forest Lake");
+    // 전역 기본 프로필을 따르지 않습니다.
+    assert_ne!(Profile::current().num_pieces(), 30);
+    assert_eq!(puzzle, Puzzle::from(&Profile::new("Forest Lake", 6, 5)));
+}
+
+#[test]
+fn refuses_puzzles_without_pieces() {
+    let empty = Puzzle {
+        num_pieces: 0,
+        name: "Nothing".to_string(),
+    };
+    assert!(matches!(
+        Profile::try_from(&empty),
+        Err(PuzzleError::BadDimensions { num_pieces: 0, .. })
+    ));
+    assert!(puzzles::Puzzle::try_from(&empty).is_err());
+}
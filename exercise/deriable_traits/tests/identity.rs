@@ -0,0 +1,90 @@
+use deriable_traits::{Puzzle, PuzzleSet};
+use proptest::prelude::*;
+use std::cmp::Ordering;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+fn hash_of(puzzle: &Puzzle) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    puzzle.hash(&mut hasher);
+    hasher.finish()
+}
+
+// 같은 이름을 대소문자만 섞어서 다시 씁니다. 그리스어, 독일어 글자도 섞이게 합니다.
+fn mixed_case(name: &str, flips: &[bool]) -> String {
+    name.chars()
+        .zip(flips.iter().cycle())
+        .map(|(c, &upper)| {
+            if upper {
+                c.to_uppercase().collect::<String>()
+            } else {
+                c.to_lowercase().collect()
+            }
+        })
+        .collect()
+}
+
+fn name() -> impl Strategy<Value = String> {
+    "[a-zA-Z0-9 ÄäÖößΣσςΑα]{0,16}"
+}
+
+fn puzzle() -> impl Strategy<Value = Puzzle> {
+    (0u32..5, name()).prop_map(|(num_pieces, name)| Puzzle { num_pieces, name })
+}
+
+proptest! {
+    #[test]
+    fn equal_puzzles_hash_the_same(a in puzzle(), b in puzzle()) {
+        if a == b {
+            prop_assert_eq!(hash_of(&a), hash_of(&b));
+        }
+    }
+
+    #[test]
+    fn case_changes_keep_the_hash(
+        a in puzzle(),
+        flips in prop::collection::vec(any::<bool>(), 1..8),
+    ) {
+        let b = Puzzle {
+            num_pieces: a.num_pieces,
+            name: mixed_case(&a.name, &flips),
+        };
+        if a == b {
+            prop_assert_eq!(hash_of(&a), hash_of(&b));
+        }
+    }
+
+    #[test]
+    fn order_agrees_with_eq(a in puzzle(), b in puzzle()) {
+        prop_assert_eq!(a.cmp(&b) == Ordering::Equal, a == b);
+        prop_assert_eq!(a.cmp(&b), b.cmp(&a).reverse());
+        prop_assert_eq!(a.partial_cmp(&b), Some(a.cmp(&b)));
+    }
+
+    #[test]
+    fn set_holds_one_of_each(puzzles in prop::collection::vec(puzzle(), 0..20)) {
+        let set: PuzzleSet = puzzles.iter().cloned().collect();
+        for puzzle in &puzzles {
+            prop_assert!(set.contains(puzzle));
+        }
+        let sorted = set.sorted();
+        prop_assert!(sorted.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+}
+
+#[test]
+fn works_as_a_hash_map_key() {
+    let mut progress = std::collections::HashMap::new();
+    progress.insert(
+        Puzzle {
+            num_pieces: 30,
+            name: "Forest Lake".to_string(),
+        },
+        12,
+    );
+    let shouting = Puzzle {
+        num_pieces: 30,
+        name: "FOREST LAKE".to_string(),
+    };
+    assert_eq!(progress.get(&shouting), Some(&12));
+}
@@ -0,0 +1,120 @@
+use deriable_traits::*;
+
+// 종류와 상관없이 같은 코드로 두고, 되돌리고, 저장하고, 불러옵니다.
+fn play_undo_and_reload<K>(puzzle: K, moves: &[K::Move]) -> Session<K>
+where
+    K: PuzzleKind + Clone + PartialEq + std::fmt::Debug,
+{
+    let start = puzzle.clone();
+    let mut session = Session::new(puzzle);
+    for &mv in moves {
+        session.play(mv).unwrap();
+    }
+    assert_eq!(session.moves(), moves.len());
+    let played = session.puzzle().clone();
+
+    let mut saved = Vec::new();
+    session.save(&mut saved).unwrap();
+    let text = String::from_utf8(saved.clone()).unwrap();
+    assert_eq!(saved_type(&text).unwrap(), K::TYPE);
+    let loaded = Session::<K>::load(saved.as_slice()).unwrap();
+    assert_eq!(loaded.puzzle(), &played);
+    assert_eq!(loaded.moves(), 0);
+
+    while session.undo() {}
+    assert_eq!(session.puzzle(), &start);
+    assert!(!session.undo());
+    loaded
+}
+
+#[test]
+fn sliding_tile() {
+    let solved = SlidingTile::new(4).unwrap();
+    let moves = [Slide::Down, Slide::Right, Slide::Up];
+    let mut loaded = play_undo_and_reload(solved, &moves);
+    assert!(!loaded.is_solved());
+    for slide in moves.iter().rev() {
+        loaded.play(slide.opposite()).unwrap();
+    }
+    assert!(loaded.is_solved());
+}
+
+#[test]
+fn sudoku() {
+    let mut givens = [0; 81];
+    givens[0] = 5;
+    givens[80] = 9;
+    let sudoku = Sudoku::new(givens).unwrap();
+    let moves = [
+        Fill {
+            row: 0,
+            col: 1,
+            digit: Some(3),
+        },
+        Fill {
+            row: 0,
+            col: 1,
+            digit: Some(4),
+        },
+    ];
+    let loaded = play_undo_and_reload(sudoku, &moves);
+    assert_eq!(loaded.puzzle().get(0, 1), Some(4));
+    assert!(loaded.puzzle().is_given(8, 8));
+}
+
+#[test]
+fn nonogram() {
+    let nonogram = Nonogram::new(vec![vec![1], vec![2]], vec![vec![2], vec![1]]).unwrap();
+    let fill = |row, col| Paint {
+        row,
+        col,
+        mark: Mark::Filled,
+    };
+    let loaded = play_undo_and_reload(nonogram, &[fill(0, 0), fill(1, 0), fill(1, 1)]);
+    assert!(loaded.is_solved());
+}
+
+#[test]
+fn illegal_moves_leave_the_game_alone() {
+    let mut session = Session::new(SlidingTile::new(3).unwrap());
+    assert_eq!(session.play(Slide::Up), Err(MoveError::NoTile));
+    assert_eq!(session.moves(), 0);
+    assert!(session.is_solved());
+}
+
+#[test]
+fn loading_checks_the_kind() {
+    let mut saved = Vec::new();
+    Session::new(SlidingTile::new(3).unwrap())
+        .save(&mut saved)
+        .unwrap();
+    assert!(saved.starts_with(b"sliding-tile\n"));
+
+    match Session::<Sudoku>::load(saved.as_slice()) {
+        Err(LoadError::WrongType { expected, found }) => {
+            assert_eq!(expected, PuzzleType::Sudoku);
+            assert_eq!(found, PuzzleType::SlidingTile);
+        }
+        other => panic!("expected WrongType, got {:?}", other),
+    }
+    assert!(matches!(
+        Session::<Sudoku>::load(&b"crossword\n"[..]),
+        Err(LoadError::UnknownType(kind)) if kind == "crossword"
+    ));
+    assert!(matches!(
+        saved_type("jigsaw\n"),
+        Err(LoadError::NotSaveable(PuzzleType::Jigsaw))
+    ));
+    assert!(matches!(
+        Session::<Sudoku>::load(&b"jigsaw\n"[..]),
+        Err(LoadError::NotSaveable(PuzzleType::Jigsaw))
+    ));
+    assert!(matches!(
+        Session::<Nonogram>::load(&b""[..]),
+        Err(LoadError::MissingType)
+    ));
+    assert!(matches!(
+        Session::<SlidingTile>::load(&b"sliding-tile\n1 2\n3\n"[..]),
+        Err(LoadError::Board(BoardError::BadLine { line: 3, .. }))
+    ));
+}
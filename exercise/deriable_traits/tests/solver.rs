@@ -0,0 +1,73 @@
+use deriable_traits::*;
+
+// 8 퍼즐에서 가장 어려운 두 배치로, 둘 다 최소 31 수가 필요합니다.
+const HARDEST_EIGHT: [&str; 2] = ["8 6 7\n2 5 4\n3 . 1\n", "6 4 7\n8 5 .\n3 2 1\n"];
+
+fn replay(puzzle: &SlidingTile, slides: &[Slide]) -> SlidingTile {
+    let mut puzzle = puzzle.clone();
+    for &slide in slides {
+        puzzle.apply(slide).unwrap();
+    }
+    puzzle
+}
+
+#[test]
+fn saved_patterns_solve_the_hardest_eight_puzzles() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("eight.pdb");
+    PatternDb::build(3, vec![vec![1, 2, 3, 4], vec![5, 6, 7, 8]])
+        .unwrap()
+        .save(&path)
+        .unwrap();
+    let patterns = PatternDb::load(&path).unwrap();
+
+    for text in HARDEST_EIGHT {
+        let puzzle = SlidingTile::decode(text).unwrap();
+        let classic = puzzle.solve().unwrap();
+        let with_patterns = SlidingSolver::new()
+            .patterns(&patterns)
+            .solve(&puzzle)
+            .unwrap();
+        assert_eq!(classic.slides.len(), 31);
+        assert_eq!(with_patterns.slides.len(), 31);
+        assert!(replay(&puzzle, &classic.slides).is_solved());
+        assert!(replay(&puzzle, &with_patterns.slides).is_solved());
+        // 패턴 데이터베이스의 값이 더 크니 덜 찾아봐도 됩니다.
+        assert!(with_patterns.stats.nodes <= classic.stats.nodes);
+    }
+}
+
+#[test]
+fn finds_a_shortcut_through_a_scramble() {
+    let solved = SlidingTile::new(4).unwrap();
+    // 같은 자리를 맴도는 수가 섞여 있어서 더 짧은 답이 있을 수 있습니다.
+    use Slide::*;
+    let scramble = [
+        Down, Down, Right, Up, Left, Down, Right, Right, Up, Up, Left, Down, Down, Down,
+    ];
+    let puzzle = replay(&solved, &scramble);
+    let solution = puzzle.solve().unwrap();
+    assert!(solution.slides.len() <= scramble.len());
+    assert_eq!(solution.slides.len() % 2, scramble.len() % 2);
+    assert!(replay(&puzzle, &solution.slides).is_solved());
+}
+
+#[test]
+fn patterns_must_match_the_puzzle() {
+    let patterns = PatternDb::build(2, vec![vec![1, 2, 3]]).unwrap();
+    let puzzle = SlidingTile::new(3).unwrap();
+    assert_eq!(
+        SlidingSolver::new()
+            .patterns(&patterns)
+            .solve(&puzzle)
+            .unwrap_err(),
+        SolveError::WrongPatterns {
+            puzzle: 3,
+            patterns: 2
+        }
+    );
+    assert!(matches!(
+        PatternDb::load("no/such/file.pdb"),
+        Err(PatternError::Open { .. })
+    ));
+}
@@ -47,6 +47,49 @@ impl Puzzle {
     }
 }
 
+impl Default for Puzzle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// 실제 퍼즐의 이름과 크기는 puzzles crate 의 Profile 이 정합니다.
+// 다시 내보낸 타입도 pub 이라 문서에 함께 실리고, pub use 에 단 주석은 원래 문서 앞에 붙습니다.
+/// The name and layout a real puzzle is made from
+pub use puzzles::Profile;
+
+impl From<&Profile> for Puzzle {
+    /// A puzzle with the profile's name and number of pieces
+    fn from(profile: &Profile) -> Self {
+        Self {
+            num_pieces: profile.num_pieces(),
+            name: profile.name.clone(),
+        }
+    }
+}
+
 fn main() {
     println!("Hello, world!");
+    let puzzle = Puzzle::new();
+    println!("{} has {} pieces", puzzle.name, puzzle.num_pieces);
+    // 42 조각짜리 "Forest Lake" 프로필로 만들어도 같은 퍼즐입니다.
+    let from_profile = Puzzle::from(&Profile::forest_lake());
+    println!(
+        "{} from its profile has {} pieces",
+        from_profile.name, from_profile.num_pieces
+    );
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn keeps_its_own_default() {
+        // 전역 기본 프로필이 아니라 이 예제의 "Forest Lake" 를 기본값으로 씁니다.
+        let puzzle = Puzzle::default();
+        assert_eq!(puzzle.num_pieces, PUZZLE_PIECES);
+        assert_eq!(puzzle.name, "Forest Lake");
+        assert_eq!(puzzle.name, Puzzle::from(&Profile::forest_lake()).name);
+    }
 }
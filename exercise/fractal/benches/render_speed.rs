@@ -0,0 +1,37 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use fractal::Fractal;
+
+pub fn render_benchmark(c: &mut Criterion) {
+    let fractal = Fractal::Mandelbrot {
+        max_iterations: 256,
+    };
+    let cpus = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let mut group = c.benchmark_group("render mandelbrot 640x480");
+    let mut counts = vec![1, 2, 4, cpus];
+    counts.sort_unstable();
+    counts.dedup();
+    for threads in counts {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(threads),
+            &threads,
+            |b, &threads| {
+                b.iter(|| {
+                    black_box(&fractal)
+                        .render_parallel(640, 480, threads)
+                        .unwrap()
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, render_benchmark);
+criterion_main!(benches);
+
+// $ cargo bench
+// CPU 가 1 개인 머신이라 스레드를 늘려도 빨라지지 않습니다. 늘어난 시간은 채널과 스레드 비용입니다.
+// 코어가 여럿인 머신에서는 코어 수까지 거의 그 배수만큼 빨라져야 합니다.
+// render mandelbrot 640x480/1   time:   [95.308 ms 96.202 ms 97.180 ms]
+// render mandelbrot 640x480/2   time:   [98.403 ms 100.28 ms 102.41 ms]
+// render mandelbrot 640x480/4   time:   [96.831 ms 97.659 ms 98.572 ms]
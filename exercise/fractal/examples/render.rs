@@ -0,0 +1,25 @@
+//! Render every fractal to a PNG: `cargo run --example render -- <dir>`
+
+use fractal::Fractal;
+use std::path::PathBuf;
+
+fn main() -> Result<(), fractal::FractalError> {
+    let dir = PathBuf::from(std::env::args().nth(1).unwrap_or_else(|| ".".to_string()));
+    let fractals = [
+        ("mandelbrot", Fractal::default()),
+        (
+            "julia",
+            Fractal::Julia {
+                c: (-0.8, 0.156),
+                max_iterations: 256,
+            },
+        ),
+        ("koch", Fractal::Koch { depth: 5 }),
+    ];
+    for (name, fractal) in fractals {
+        let path = dir.join(format!("{}.png", name));
+        fractal.render(480, 360)?.save_png(&path)?;
+        println!("Wrote {}", path.display());
+    }
+    Ok(())
+}
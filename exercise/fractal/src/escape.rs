@@ -0,0 +1,64 @@
+use crate::{palette, to_point};
+
+/// Once `|z|` passes this, it is certain to run off to infinity
+const ESCAPE_RADIUS: f64 = 2.0;
+
+/// Color each pixel by how fast `z = z² + c` escapes
+///
+/// With `julia` unset this is the Mandelbrot set: `c` is the pixel and `z`
+/// starts at zero. Otherwise `c` is fixed and `z` starts at the pixel.
+pub(crate) fn render(
+    julia: Option<(f64, f64)>,
+    max_iterations: u32,
+    width: u32,
+    height: u32,
+    first_row: u32,
+    pixels: &mut [u8],
+) {
+    let (center, span) = match julia {
+        None => ((-0.75, 0.0), 2.6),
+        Some(_) => ((0.0, 0.0), 3.2),
+    };
+    for (row, line) in pixels.chunks_exact_mut(width as usize * 4).enumerate() {
+        let y = first_row + row as u32;
+        for (x, pixel) in line.chunks_exact_mut(4).enumerate() {
+            let point = to_point(center, span, width, height, x as u32, y);
+            let (z, c) = match julia {
+                None => ((0.0, 0.0), point),
+                Some(c) => (point, c),
+            };
+            let color = match escape_time(z, c, max_iterations) {
+                None => [0, 0, 0, 255],
+                Some(n) => palette(n / f64::from(max_iterations)),
+            };
+            pixel.copy_from_slice(&color);
+        }
+    }
+}
+
+/// Smoothed number of iterations before `z` escapes, or `None` if it never does
+fn escape_time(mut z: (f64, f64), c: (f64, f64), max_iterations: u32) -> Option<f64> {
+    for n in 0..max_iterations {
+        let norm = z.0 * z.0 + z.1 * z.1;
+        if norm > ESCAPE_RADIUS * ESCAPE_RADIUS {
+            // 정수 횟수만 쓰면 색이 계단처럼 끊어집니다.
+            // 빠져나갈 때의 크기로 소수 부분을 채워 부드럽게 만듭니다.
+            let smooth = f64::from(n) + 1.0 - norm.ln().ln() / std::f64::consts::LN_2;
+            return Some(smooth.max(0.0));
+        }
+        z = (z.0 * z.0 - z.1 * z.1 + c.0, 2.0 * z.0 * z.1 + c.1);
+    }
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn points_in_the_set_never_escape() {
+        assert_eq!(escape_time((0.0, 0.0), (0.0, 0.0), 1000), None);
+        assert_eq!(escape_time((0.0, 0.0), (-1.0, 0.0), 1000), None);
+        assert!(escape_time((0.0, 0.0), (1.0, 1.0), 1000).unwrap() < 3.0);
+    }
+}
@@ -0,0 +1,83 @@
+use crate::{palette, to_point};
+
+/// Fill the inside of the snowflake, leaving the outside a dark background
+pub(crate) fn render(depth: u8, width: u32, height: u32, first_row: u32, pixels: &mut [u8]) {
+    let outline = snowflake(depth);
+    let background = [16, 16, 32, 255];
+    let mut crossings = Vec::new();
+    // 한 줄씩 외곽선과 만나는 점을 구해서 그 사이를 칠합니다 (scanline fill).
+    for (row, line) in pixels.chunks_exact_mut(width as usize * 4).enumerate() {
+        let y = first_row + row as u32;
+        let (_, py) = to_point((0.0, 0.0), 2.4, width, height, 0, y);
+        crossings.clear();
+        for (i, &(ax, ay)) in outline.iter().enumerate() {
+            let (bx, by) = outline[(i + 1) % outline.len()];
+            if (ay <= py) != (by <= py) {
+                crossings.push(ax + (py - ay) / (by - ay) * (bx - ax));
+            }
+        }
+        crossings.sort_by(f64::total_cmp);
+        for (x, pixel) in line.chunks_exact_mut(4).enumerate() {
+            let (px, _) = to_point((0.0, 0.0), 2.4, width, height, x as u32, y);
+            let inside = crossings.iter().filter(|&&cx| cx < px).count() % 2 == 1;
+            let color = if inside {
+                palette(0.35 + 0.3 * (px * px + py * py).sqrt())
+            } else {
+                background
+            };
+            pixel.copy_from_slice(&color);
+        }
+    }
+}
+
+/// Corners of the snowflake, clockwise, centered on the origin
+fn snowflake(depth: u8) -> Vec<(f64, f64)> {
+    // 반지름 1 인 원에 내접하는 정삼각형에서 시작합니다. y 는 아래로 커집니다.
+    let mut points: Vec<(f64, f64)> = (0..3)
+        .map(|i| {
+            let angle =
+                -std::f64::consts::FRAC_PI_2 + f64::from(i) * 2.0 * std::f64::consts::PI / 3.0;
+            (angle.cos(), angle.sin())
+        })
+        .collect();
+    for _ in 0..depth {
+        let mut next = Vec::with_capacity(points.len() * 4);
+        for (i, &a) in points.iter().enumerate() {
+            let b = points[(i + 1) % points.len()];
+            let d = ((b.0 - a.0) / 3.0, (b.1 - a.1) / 3.0);
+            let p = (a.0 + d.0, a.1 + d.1);
+            let q = (a.0 + 2.0 * d.0, a.1 + 2.0 * d.1);
+            // 가운데 1/3 을 바깥쪽으로 60도 꺾어 뾰족한 부분을 만듭니다.
+            let (sin, cos) = (-std::f64::consts::FRAC_PI_3).sin_cos();
+            let tip = (p.0 + d.0 * cos - d.1 * sin, p.1 + d.0 * sin + d.1 * cos);
+            next.extend([a, p, tip, q]);
+        }
+        points = next;
+    }
+    points
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn every_level_has_four_times_the_edges() {
+        assert_eq!(snowflake(0).len(), 3);
+        assert_eq!(snowflake(1).len(), 12);
+        assert_eq!(snowflake(4).len(), 3 * 4usize.pow(4));
+    }
+
+    #[test]
+    fn tips_point_outwards() {
+        // 첫 번째 꼭짓점 다음의 뾰족한 점은 원래 삼각형보다 원점에서 멀어야 합니다.
+        let points = snowflake(1);
+        let edge_middle = (
+            (points[0].0 + points[4].0) / 2.0,
+            (points[0].1 + points[4].1) / 2.0,
+        );
+        let tip = points[2];
+        let distance = |(x, y): (f64, f64)| (x * x + y * y).sqrt();
+        assert!(distance(tip) > distance(edge_middle));
+    }
+}
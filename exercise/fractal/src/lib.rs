@@ -0,0 +1,267 @@
+// exceptions 예제에서 가정했던 Fractal 라이브러리입니다.
+// 퍼즐 라이브러리는 이 crate 의 FractalError 를 그대로 내보내지 않고 자기 에러로 감싸서 씁니다.
+
+use log::info;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+mod escape;
+mod koch;
+mod parallel;
+
+/// Deepest Koch snowflake we are willing to draw
+///
+/// Every level has four times as many edges as the one before.
+pub const MAX_KOCH_DEPTH: u8 = 8;
+/// Largest canvas side in pixels
+pub const MAX_SIDE: u32 = 16384;
+
+/// Something to draw
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Fractal {
+    /// The Mandelbrot set, `z = z² + c` starting from zero for every `c`
+    Mandelbrot { max_iterations: u32 },
+    /// The Julia set for a fixed `c = (re, im)`
+    Julia { c: (f64, f64), max_iterations: u32 },
+    /// Koch snowflake, a triangle whose sides are bent `depth` times
+    Koch { depth: u8 },
+}
+
+impl Default for Fractal {
+    fn default() -> Self {
+        Fractal::Mandelbrot {
+            max_iterations: 256,
+        }
+    }
+}
+
+impl Fractal {
+    /// Draw the whole fractal, scaled to fit a `width` x `height` canvas
+    pub fn render(&self, width: u32, height: u32) -> Result<Canvas, FractalError> {
+        self.check(width, height)?;
+        let mut pixels = vec![0; width as usize * height as usize * 4];
+        self.render_rows(width, height, 0, &mut pixels);
+        info!("Rendered {:?} at {}x{}", self, width, height);
+        Ok(Canvas {
+            width,
+            height,
+            pixels,
+        })
+    }
+
+    /// Like [`Fractal::render`], but split into bands of rows drawn by `threads` workers
+    ///
+    /// With `threads` set to 0, one worker per CPU is used, but never more
+    /// than there are bands of rows. Any other count is used as it is. The
+    /// picture is exactly the same as the one `render` draws.
+    pub fn render_parallel(
+        &self,
+        width: u32,
+        height: u32,
+        threads: usize,
+    ) -> Result<Canvas, FractalError> {
+        self.check(width, height)?;
+        let threads = parallel::workers(height, threads);
+        let pixels = parallel::render(self, width, height, threads);
+        info!(
+            "Rendered {:?} at {}x{} on {} threads",
+            self, width, height, threads
+        );
+        Ok(Canvas {
+            width,
+            height,
+            pixels,
+        })
+    }
+
+    /// Make sure the fractal can be drawn at this size
+    fn check(&self, width: u32, height: u32) -> Result<(), FractalError> {
+        if width == 0 || height == 0 || width > MAX_SIDE || height > MAX_SIDE {
+            return Err(FractalError::BadSize { width, height });
+        }
+        match *self {
+            Fractal::Mandelbrot { max_iterations } | Fractal::Julia { max_iterations, .. }
+                if max_iterations == 0 =>
+            {
+                Err(FractalError::NoIterations)
+            }
+            Fractal::Julia { c: (re, im), .. } if !re.is_finite() || !im.is_finite() => {
+                Err(FractalError::BadConstant(re, im))
+            }
+            Fractal::Koch { depth } if depth > MAX_KOCH_DEPTH => {
+                Err(FractalError::SadSnowFlake(depth))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Fill `pixels` with the rows starting at `first_row`
+    ///
+    /// `pixels` holds whole RGBA rows of the full `width` x `height` canvas.
+    fn render_rows(&self, width: u32, height: u32, first_row: u32, pixels: &mut [u8]) {
+        match *self {
+            Fractal::Mandelbrot { max_iterations } => {
+                escape::render(None, max_iterations, width, height, first_row, pixels)
+            }
+            Fractal::Julia { c, max_iterations } => {
+                escape::render(Some(c), max_iterations, width, height, first_row, pixels)
+            }
+            Fractal::Koch { depth } => koch::render(depth, width, height, first_row, pixels),
+        }
+    }
+}
+
+/// An RGBA picture, row by row from the top left, 4 bytes per pixel
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Canvas {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl Canvas {
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// The raw RGBA bytes
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// Hand over the raw RGBA bytes
+    pub fn into_pixels(self) -> Vec<u8> {
+        self.pixels
+    }
+
+    /// Color of the pixel at `(x, y)`
+    pub fn pixel(&self, x: u32, y: u32) -> [u8; 4] {
+        let i = (y as usize * self.width as usize + x as usize) * 4;
+        [
+            self.pixels[i],
+            self.pixels[i + 1],
+            self.pixels[i + 2],
+            self.pixels[i + 3],
+        ]
+    }
+
+    /// Write the canvas as a PNG file
+    pub fn save_png<P: AsRef<Path>>(&self, path: P) -> Result<(), FractalError> {
+        let path = path.as_ref();
+        image::save_buffer(
+            path,
+            &self.pixels,
+            self.width,
+            self.height,
+            image::ColorType::Rgba8,
+        )
+        .map_err(|e| FractalError::Png {
+            path: path.to_path_buf(),
+            source: e.into(),
+        })?;
+        info!(
+            "Saved {}x{} PNG to {}",
+            self.width,
+            self.height,
+            path.display()
+        );
+        Ok(())
+    }
+}
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum FractalError {
+    #[error("The snowflake is too deep to draw: depth {0}, at most {MAX_KOCH_DEPTH}")]
+    SadSnowFlake(u8),
+    #[error("Can't draw on a {width} x {height} canvas")]
+    BadSize { width: u32, height: u32 },
+    #[error("At least one iteration is needed")]
+    NoIterations,
+    #[error("The Julia constant ({0}, {1}) is not a number")]
+    BadConstant(f64, f64),
+    // PNG 인코더의 에러 타입은 감춰두고 원인으로만 달아 둡니다.
+    #[error("Couldn't save the PNG to {}", path.display())]
+    Png {
+        path: PathBuf,
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+}
+
+/// Turn a value between 0 and 1 into a color, dark blue through orange to white
+fn palette(t: f64) -> [u8; 4] {
+    let t = t.clamp(0.0, 1.0);
+    // 9t(1-t)³ 같은 다항식을 쓰면 그라데이션이 부드럽게 이어집니다.
+    let r = 9.0 * (1.0 - t) * t * t * t;
+    let g = 15.0 * (1.0 - t) * (1.0 - t) * t * t;
+    let b = 8.5 * (1.0 - t) * (1.0 - t) * (1.0 - t) * t;
+    let channel = |v: f64| (v.clamp(0.0, 1.0) * 255.0) as u8;
+    [channel(r), channel(g), channel(b), 255]
+}
+
+/// Map pixel `(x, y)` to a point, fitting `span` x `span` around `center` into the canvas
+///
+/// Pixel centers are used, so mirrored pixels land on exactly mirrored points.
+fn to_point(center: (f64, f64), span: f64, width: u32, height: u32, x: u32, y: u32) -> (f64, f64) {
+    let scale = span / f64::from(width.min(height));
+    (
+        center.0 + (f64::from(x) + 0.5 - f64::from(width) / 2.0) * scale,
+        center.1 + (f64::from(y) + 0.5 - f64::from(height) / 2.0) * scale,
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn mandelbrot_is_black_inside_and_colored_outside() {
+        let canvas = Fractal::default().render(64, 48).unwrap();
+        assert_eq!(canvas.pixels().len(), 64 * 48 * 4);
+        // 가운데는 집합 안쪽, 모서리는 바로 빠져나갑니다.
+        assert_eq!(canvas.pixel(32, 24), [0, 0, 0, 255]);
+        assert_ne!(canvas.pixel(0, 0), [0, 0, 0, 255]);
+    }
+
+    #[test]
+    fn julia_sets_are_point_symmetric() {
+        let julia = Fractal::Julia {
+            c: (-0.8, 0.156),
+            max_iterations: 100,
+        };
+        let canvas = julia.render(40, 30).unwrap();
+        for y in 0..30 {
+            for x in 0..40 {
+                assert_eq!(canvas.pixel(x, y), canvas.pixel(39 - x, 29 - y));
+            }
+        }
+    }
+
+    #[test]
+    fn snowflake_is_filled_in_the_middle() {
+        for depth in [0, 3] {
+            let canvas = Fractal::Koch { depth }.render(50, 50).unwrap();
+            assert_ne!(canvas.pixel(25, 25), canvas.pixel(0, 0));
+        }
+    }
+
+    #[test]
+    fn rejects_what_it_cant_draw() {
+        assert!(matches!(
+            Fractal::Koch { depth: 9 }.render(10, 10),
+            Err(FractalError::SadSnowFlake(9))
+        ));
+        assert!(matches!(
+            Fractal::default().render(0, 10),
+            Err(FractalError::BadSize { .. })
+        ));
+        assert!(matches!(
+            Fractal::Mandelbrot { max_iterations: 0 }.render(10, 10),
+            Err(FractalError::NoIterations)
+        ));
+    }
+}
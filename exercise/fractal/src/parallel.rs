@@ -0,0 +1,126 @@
+// kitchen 예제의 sleep 대신, 진짜로 CPU 를 바쁘게 만드는 일입니다.
+// 그림을 몇 줄씩 띠(band)로 나눠 channels 예제처럼 worker 스레드들에게 나눠 줍니다.
+
+use crate::Fractal;
+use crossbeam::channel;
+use log::debug;
+use std::ops::Range;
+use std::thread;
+
+/// Rows in one band
+///
+/// Small enough that the slow parts of the picture are spread over the
+/// workers, big enough that sending bands around costs next to nothing.
+const BAND_ROWS: u32 = 16;
+
+/// A band of rows to draw, numbered from the top
+struct Band {
+    index: usize,
+    rows: Range<u32>,
+}
+
+/// How many workers to start for `threads` asked for
+///
+/// 0 means one per CPU, but never more than there are bands to draw.
+/// Any other count is used as it is.
+pub(crate) fn workers(height: u32, threads: usize) -> usize {
+    if threads != 0 {
+        return threads;
+    }
+    let cpus = thread::available_parallelism().map_or(1, |n| n.get());
+    // 할 일이 없는 worker 는 스레드만 차지하니 띠 개수에서 자릅니다.
+    cpus.min(height.div_ceil(BAND_ROWS) as usize).max(1)
+}
+
+/// Draw with exactly `threads` workers; see [`workers`]
+pub(crate) fn render(fractal: &Fractal, width: u32, height: u32, threads: usize) -> Vec<u8> {
+    let row_len = width as usize * 4;
+    let bands: Vec<Band> = (0..height)
+        .step_by(BAND_ROWS as usize)
+        .enumerate()
+        .map(|(index, first)| Band {
+            index,
+            rows: first..(first + BAND_ROWS).min(height),
+        })
+        .collect();
+
+    // 일감 채널은 bounded 라서 일을 나눠 주는 쪽이 worker 보다 너무 앞서 나가지 않습니다.
+    // 결과 채널도 bounded 이니, 일을 나눠 주는 건 따로 스레드를 두고
+    // main 스레드는 결과를 받기만 합니다. 그래야 서로 기다리다 멈추는 일(deadlock)이 없습니다.
+    let (bands_tx, bands_rx) = channel::bounded::<Band>(threads);
+    let (done_tx, done_rx) = channel::bounded::<(usize, Vec<u8>)>(threads);
+    let mut pixels = vec![0; row_len * height as usize];
+
+    thread::scope(|scope| {
+        scope.spawn(move || {
+            for band in bands {
+                if bands_tx.send(band).is_err() {
+                    break;
+                }
+            }
+        });
+        for worker in 0..threads {
+            let bands_rx = bands_rx.clone();
+            let done_tx = done_tx.clone();
+            scope.spawn(move || {
+                for band in bands_rx {
+                    let mut buffer = vec![0; row_len * band.rows.len()];
+                    fractal.render_rows(width, height, band.rows.start, &mut buffer);
+                    debug!("Worker {} drew rows {:?}", worker, band.rows);
+                    if done_tx.send((band.index, buffer)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        // 복제본만 worker 에게 넘겼으니 원본은 여기서 버려야 결과 채널이 닫힙니다.
+        drop(done_tx);
+
+        // 띠는 끝나는 순서대로 오지만, 번호를 보고 제자리에 붙여 넣습니다.
+        let band_len = row_len * BAND_ROWS as usize;
+        for (index, buffer) in done_rx {
+            let start = index * band_len;
+            pixels[start..start + buffer.len()].copy_from_slice(&buffer);
+        }
+    });
+    pixels
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn matches_the_single_threaded_picture() {
+        let fractals = [
+            Fractal::default(),
+            Fractal::Julia {
+                c: (-0.4, 0.6),
+                max_iterations: 64,
+            },
+            Fractal::Koch { depth: 3 },
+        ];
+        // 높이 37 은 띠 크기로 나누어 떨어지지 않고, 스레드가 띠보다 많은 경우도 봅니다.
+        for fractal in fractals {
+            let expected = fractal.render(45, 37).unwrap();
+            for threads in [1, 2, 3, 8, 0] {
+                assert_eq!(fractal.render_parallel(45, 37, threads).unwrap(), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn only_caps_the_automatic_worker_count() {
+        let cpus = thread::available_parallelism().map_or(1, |n| n.get());
+        assert_eq!(workers(37, 0), cpus.min(3));
+        assert_eq!(workers(1, 0), 1);
+        // 직접 정한 개수는 그대로 씁니다.
+        assert_eq!(workers(37, 8), 8);
+        assert_eq!(workers(10_000, 1), 1);
+    }
+
+    #[test]
+    fn checks_the_size_first() {
+        assert!(Fractal::default().render_parallel(10, 0, 4).is_err());
+    }
+}
@@ -0,0 +1,27 @@
+use fractal::{Fractal, FractalError};
+
+#[test]
+fn saves_a_png_that_reads_back_the_same() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("julia.png");
+    let julia = Fractal::Julia {
+        c: (0.285, 0.01),
+        max_iterations: 64,
+    };
+    let canvas = julia.render(48, 32).unwrap();
+    canvas.save_png(&path).unwrap();
+
+    let loaded = image::open(&path).unwrap().into_rgba8();
+    assert_eq!((loaded.width(), loaded.height()), (48, 32));
+    assert_eq!(loaded.as_raw(), canvas.pixels());
+}
+
+#[test]
+fn reports_where_the_png_couldnt_go() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("missing").join("koch.png");
+    let canvas = Fractal::Koch { depth: 2 }.render(16, 16).unwrap();
+    let err = canvas.save_png(&path).unwrap_err();
+    assert!(matches!(err, FractalError::Png { .. }));
+    assert!(err.to_string().contains("koch.png"));
+}
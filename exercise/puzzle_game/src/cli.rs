@@ -0,0 +1,172 @@
+use clap::{Parser, Subcommand};
+use log::LevelFilter;
+use puzzles::Difficulty;
+use std::path::PathBuf;
+
+/// Play jigsaw puzzles in the terminal
+#[derive(Debug, Parser)]
+#[command(version)]
+pub struct Cli {
+    /// Puzzle file to load, and to save to during play
+    #[arg(short, long, default_value = "puzzle.dat", global = true)]
+    pub file: PathBuf,
+
+    /// Make a fresh puzzle of this size instead of loading the file, e.g. `8x6`
+    #[arg(short, long, value_name = "WxH", value_parser = parse_size, global = true)]
+    pub generate: Option<(u16, u16)>,
+
+    /// Seed for `--generate`; the same seed always makes the same puzzle
+    #[arg(long, default_value_t = 0, global = true)]
+    pub seed: u64,
+
+    /// Fail when the file can't be loaded instead of playing the default puzzle
+    #[arg(long, global = true)]
+    pub strict: bool,
+
+    /// One of off, error, warn, info, debug or trace. `RUST_LOG` still wins when set.
+    #[arg(long, default_value = "warn", global = true)]
+    pub log_level: LevelFilter,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Subcommand)]
+pub enum Command {
+    /// Play the puzzle (the default)
+    Play,
+    /// Describe the puzzle without playing it
+    Info,
+    /// Show the saved moves one at a time
+    Replay,
+    /// Check that the file loads and can be solved
+    Validate,
+    /// Browse the puzzles in a directory
+    List {
+        /// Directory to look for `.dat` puzzle files in
+        #[arg(default_value = ".")]
+        dir: PathBuf,
+        /// Only puzzles with at least this many pieces
+        #[arg(long, value_name = "N")]
+        min_pieces: Option<u32>,
+        /// Only puzzles with at most this many pieces
+        #[arg(long, value_name = "N")]
+        max_pieces: Option<u32>,
+        /// Only puzzles whose name contains this, ignoring case
+        #[arg(long, value_name = "TEXT")]
+        name: Option<String>,
+        /// Only puzzles of this difficulty: easy, medium, hard or expert
+        #[arg(long)]
+        difficulty: Option<Difficulty>,
+        /// Only solved puzzles
+        #[arg(long, conflicts_with = "unsolved")]
+        solved: bool,
+        /// Only puzzles that aren't solved yet
+        #[arg(long)]
+        unsolved: bool,
+    },
+    /// Cut a PNG into pieces for a graphical front end
+    Cut {
+        /// Picture to cut up
+        image: PathBuf,
+        /// How many pieces across and down, e.g. `4x3`
+        #[arg(short, long, value_name = "WxH", value_parser = parse_size, default_value = "7x6")]
+        pieces: (u16, u16),
+        /// Directory for the piece images, the puzzle file and the manifest
+        #[arg(short, long, default_value = "pieces")]
+        out: PathBuf,
+    },
+}
+
+/// Parse `WIDTHxHEIGHT`
+fn parse_size(size: &str) -> Result<(u16, u16), String> {
+    let (width, height) = size
+        .split_once(['x', 'X'])
+        .ok_or_else(|| format!("expected WIDTHxHEIGHT, got {:?}", size))?;
+    let parse = |n: &str| {
+        n.trim()
+            .parse::<u16>()
+            .map_err(|e| format!("{:?} is not a valid size: {}", n, e))
+    };
+    Ok((parse(width)?, parse(height)?))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parses_sizes() {
+        assert_eq!(parse_size("8x6"), Ok((8, 6)));
+        assert_eq!(parse_size("10X3"), Ok((10, 3)));
+        assert!(parse_size("8").is_err());
+        assert!(parse_size("8xq").is_err());
+    }
+
+    #[test]
+    fn defaults_to_playing_puzzle_dat() {
+        let cli = Cli::parse_from(["puzzle_game"]);
+        assert_eq!(cli.file, PathBuf::from("puzzle.dat"));
+        assert_eq!(cli.command, None);
+        assert!(!cli.strict);
+    }
+
+    #[test]
+    fn options_work_after_the_subcommand() {
+        let cli = Cli::parse_from(["puzzle_game", "info", "--generate", "3x2", "--seed", "7"]);
+        assert_eq!(cli.command, Some(Command::Info));
+        assert_eq!(cli.generate, Some((3, 2)));
+        assert_eq!(cli.seed, 7);
+    }
+
+    #[test]
+    fn list_filters() {
+        let cli = Cli::parse_from([
+            "puzzle_game",
+            "list",
+            "shelf",
+            "--min-pieces",
+            "500",
+            "--difficulty",
+            "hard",
+            "--unsolved",
+        ]);
+        match cli.command {
+            Some(Command::List {
+                dir,
+                min_pieces,
+                difficulty,
+                unsolved,
+                solved,
+                ..
+            }) => {
+                assert_eq!(dir, PathBuf::from("shelf"));
+                assert_eq!(min_pieces, Some(500));
+                assert_eq!(difficulty, Some(Difficulty::Hard));
+                assert!(unsolved && !solved);
+            }
+            other => panic!("expected list, got {:?}", other),
+        }
+        assert!(Cli::try_parse_from(["puzzle_game", "list", "--solved", "--unsolved"]).is_err());
+    }
+
+    #[test]
+    fn cut_takes_an_image_and_an_output_directory() {
+        let cli = Cli::parse_from(["puzzle_game", "cut", "lake.png", "--pieces", "4x3"]);
+        assert_eq!(
+            cli.command,
+            Some(Command::Cut {
+                image: PathBuf::from("lake.png"),
+                pieces: (4, 3),
+                out: PathBuf::from("pieces"),
+            })
+        );
+        assert_eq!(cli.generate, None);
+
+        let cli = Cli::parse_from(["puzzle_game", "cut", "lake.png"]);
+        assert!(matches!(
+            cli.command,
+            Some(Command::Cut { pieces: (7, 6), .. })
+        ));
+    }
+}
@@ -0,0 +1,327 @@
+use anyhow::{anyhow, bail, Context, Result};
+use log::{debug, info};
+use puzzles::{Board, Move, Puzzle};
+use std::fmt::Write as _;
+use std::io::{BufRead, Write};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+const HELP: &str = "\
+Commands:
+  place <id> <x> <y>  put a piece on the board
+  rotate <id>         turn a piece a quarter clockwise
+  undo                take the last move back
+  redo                make the last undone move again
+  hint                suggest a move
+  save                save the puzzle
+  quit                stop playing";
+
+/// One line of player input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    Place { id: u16, x: u16, y: u16 },
+    Rotate(u16),
+    Undo,
+    Redo,
+    Hint,
+    Save,
+    Help,
+    Quit,
+}
+
+impl FromStr for Command {
+    type Err = anyhow::Error;
+
+    fn from_str(line: &str) -> Result<Self> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let number = |i: usize, what: &str| -> Result<u16> {
+            let word = words
+                .get(i)
+                .ok_or_else(|| anyhow!("missing the {}", what))?;
+            word.parse()
+                .with_context(|| format!("{:?} is not a valid {}", word, what))
+        };
+        let command = match words.first().copied() {
+            Some("place" | "p") => Command::Place {
+                id: number(1, "piece id")?,
+                x: number(2, "x position")?,
+                y: number(3, "y position")?,
+            },
+            Some("rotate" | "r") => Command::Rotate(number(1, "piece id")?),
+            Some("undo" | "u") => Command::Undo,
+            Some("redo") => Command::Redo,
+            Some("hint" | "h") => Command::Hint,
+            Some("save" | "s") => Command::Save,
+            Some("help" | "?") => Command::Help,
+            Some("quit" | "q" | "exit") => Command::Quit,
+            Some(other) => bail!("unknown command {:?}, type `help` for a list", other),
+            None => bail!("type a command, or `help` for a list"),
+        };
+        Ok(command)
+    }
+}
+
+/// A puzzle being played on a board
+pub struct Game {
+    puzzle: Puzzle,
+    /// Keeps the move history, so `undo` and `redo` come for free
+    board: Board,
+    /// Worked out the first time a hint is asked for
+    solution: Option<Board>,
+    save_path: PathBuf,
+}
+
+impl Game {
+    /// Start a game, replaying any moves saved with the puzzle
+    pub fn new(puzzle: Puzzle, save_path: impl Into<PathBuf>) -> Result<Self> {
+        let board = puzzle.resume().context("Couldn't replay the saved moves")?;
+        Ok(Game {
+            board,
+            puzzle,
+            solution: None,
+            save_path: save_path.into(),
+        })
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.board.is_complete()
+    }
+
+    /// Read commands until the puzzle is solved, the player quits or input runs out
+    ///
+    /// Returns whether the puzzle was solved.
+    pub fn play<R: BufRead, W: Write>(&mut self, input: R, mut output: W) -> Result<bool> {
+        writeln!(output, "Playing {}", self.puzzle.name)?;
+        writeln!(output, "{}", self.render())?;
+        write!(output, "> ")?;
+        output.flush()?;
+        for line in input.lines() {
+            let line = line.context("Couldn't read a command")?;
+            debug!("Command: {:?}", line);
+            // 퍼즐 라이브러리의 에러도 여기서 친절한 메세지로 바뀝니다.
+            // anyhow 의 `{:#}` 는 context 를 원인까지 한 줄로 이어서 보여줍니다.
+            match line.parse().and_then(|command| self.execute(command)) {
+                Ok(Some(message)) => writeln!(output, "{}", message)?,
+                Ok(None) => return Ok(false),
+                Err(e) => writeln!(output, "Oops: {:#}", e)?,
+            }
+            if self.is_solved() {
+                writeln!(output, "{}", self.render())?;
+                writeln!(output, "Solved! Well done.")?;
+                info!(
+                    "Solved {} in {} moves",
+                    self.puzzle.name,
+                    self.board.history().len()
+                );
+                return Ok(true);
+            }
+            write!(output, "> ")?;
+            output.flush()?;
+        }
+        Ok(false)
+    }
+
+    /// Run one command; `None` means the player wants to stop
+    pub fn execute(&mut self, command: Command) -> Result<Option<String>> {
+        let message = match command {
+            Command::Place { id, x, y } => {
+                let piece = *self.unplaced(id)?;
+                self.board
+                    .place(piece, x, y)
+                    .with_context(|| format!("Couldn't place piece {} at ({}, {})", id, x, y))?;
+                self.render()
+            }
+            Command::Rotate(id) => {
+                let piece = self.unplaced(id)?;
+                let index = self
+                    .puzzle
+                    .pieces
+                    .iter()
+                    .position(|p| p.id == piece.id)
+                    .expect("unplaced() found the piece");
+                self.puzzle.pieces[index].rotate();
+                self.describe(&self.puzzle.pieces[index])
+            }
+            Command::Undo => {
+                let mv = self.board.undo().context("There is nothing to undo")?;
+                format!("Undid: {}\n{}", describe_move(mv), self.render())
+            }
+            Command::Redo => {
+                let mv = self.board.redo().context("There is nothing to redo")?;
+                format!("Redid: {}\n{}", describe_move(mv), self.render())
+            }
+            Command::Hint => self.hint()?,
+            Command::Save => {
+                // 보드의 기록을 퍼즐과 함께 저장해 두면 나중에 그대로 이어서 할 수 있습니다.
+                self.puzzle.history = self.board.history().to_vec();
+                self.puzzle
+                    .save(&self.save_path)
+                    .with_context(|| format!("Couldn't save to {}", self.save_path.display()))?;
+                format!("Saved to {}", self.save_path.display())
+            }
+            Command::Help => HELP.to_string(),
+            Command::Quit => return Ok(None),
+        };
+        Ok(Some(message))
+    }
+
+    /// Make a recorded move directly, as when replaying a saved game
+    pub fn apply(&mut self, mv: Move) -> Result<()> {
+        self.board
+            .apply(mv)
+            .with_context(|| format!("Couldn't replay {}", describe_move(mv)))
+    }
+
+    /// ASCII picture of the board and the pieces still in the box
+    pub fn render(&self) -> String {
+        let mut out = String::from("   ");
+        for x in 0..self.board.width() {
+            let _ = write!(out, "{:>3} ", x);
+        }
+        for y in 0..self.board.height() {
+            let _ = write!(out, "\n{:>3}", y);
+            for x in 0..self.board.width() {
+                match self.board.get(x, y) {
+                    Some(piece) => {
+                        let _ = write!(out, "[{:>2}]", piece.id);
+                    }
+                    None => out.push_str("  . "),
+                }
+            }
+        }
+        let left: Vec<String> = self
+            .puzzle
+            .pieces
+            .iter()
+            .filter(|piece| self.board.position_of(piece.id).is_none())
+            .map(|piece| piece.id.to_string())
+            .collect();
+        if !left.is_empty() {
+            let _ = write!(out, "\nPieces left: {}", left.join(" "));
+        }
+        out
+    }
+
+    fn hint(&mut self) -> Result<String> {
+        if self.solution.is_none() {
+            let solution = self
+                .puzzle
+                .solve()
+                .context("This puzzle can't be solved, so there are no hints")?;
+            self.solution = Some(solution.board);
+        }
+        let solution = self.solution.as_ref().expect("solution was just computed");
+        for y in 0..solution.height() {
+            for x in 0..solution.width() {
+                let Some(target) = solution.get(x, y) else {
+                    continue;
+                };
+                if self.board.get(x, y).is_some() || self.board.position_of(target.id).is_some() {
+                    continue;
+                }
+                let current = self
+                    .puzzle
+                    .piece(target.id)
+                    .expect("the solution only uses pieces from the puzzle");
+                let turns = (target.rotation.quarters() + 4 - current.rotation.quarters()) % 4;
+                return Ok(match turns {
+                    0 => format!("Try piece {} at ({}, {})", target.id, x, y),
+                    _ => format!(
+                        "Rotate piece {} {} time(s), then place it at ({}, {})",
+                        target.id, turns, x, y
+                    ),
+                });
+            }
+        }
+        bail!("No hint from here; try undoing a few moves")
+    }
+
+    fn unplaced(&self, id: u16) -> Result<&puzzles::Piece> {
+        let piece = self
+            .puzzle
+            .piece(id)
+            .with_context(|| format!("There is no piece {}", id))?;
+        if let Some((x, y)) = self.board.position_of(id) {
+            bail!("Piece {} is already on the board at ({}, {})", id, x, y);
+        }
+        Ok(piece)
+    }
+
+    fn describe(&self, piece: &puzzles::Piece) -> String {
+        format!(
+            "Piece {} is turned {} degrees",
+            piece.id,
+            u16::from(piece.rotation.quarters()) * 90
+        )
+    }
+}
+
+/// Say what a move does, in words
+pub fn describe_move(mv: Move) -> String {
+    match mv {
+        Move::Place { piece, x, y } => format!("piece {} placed at ({}, {})", piece.id, x, y),
+        Move::Remove { piece, x, y } => format!("piece {} taken off ({}, {})", piece.id, x, y),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn play(script: &str) -> (bool, String) {
+        let mut game = Game::new(Puzzle::generate(3, 2, 1).unwrap(), "unused.dat").unwrap();
+        let mut output = Vec::new();
+        let solved = game.play(script.as_bytes(), &mut output).unwrap();
+        (solved, String::from_utf8(output).unwrap())
+    }
+
+    #[test]
+    fn parses_commands() {
+        assert_eq!(
+            "place 3 1 0".parse::<Command>().unwrap(),
+            Command::Place { id: 3, x: 1, y: 0 }
+        );
+        assert_eq!("r 4".parse::<Command>().unwrap(), Command::Rotate(4));
+        assert_eq!("  quit ".parse::<Command>().unwrap(), Command::Quit);
+        assert!("place 3 1".parse::<Command>().is_err());
+        assert!("place x 1 0".parse::<Command>().is_err());
+        assert!("dance".parse::<Command>().is_err());
+    }
+
+    #[test]
+    fn hints_lead_to_a_solution() {
+        let mut game = Game::new(Puzzle::generate(4, 3, 9).unwrap(), "unused.dat").unwrap();
+        // 힌트대로만 두면 퍼즐이 풀려야 합니다.
+        while !game.is_solved() {
+            let hint = game.execute(Command::Hint).unwrap().unwrap();
+            let numbers: Vec<u16> = hint
+                .split(|c: char| !c.is_ascii_digit())
+                .filter_map(|word| word.parse().ok())
+                .collect();
+            let (id, x, y) = match numbers[..] {
+                [id, turns, x, y] => {
+                    for _ in 0..turns {
+                        game.execute(Command::Rotate(id)).unwrap();
+                    }
+                    (id, x, y)
+                }
+                [id, x, y] => (id, x, y),
+                _ => panic!("unexpected hint {:?}", hint),
+            };
+            game.execute(Command::Place { id, x, y }).unwrap();
+        }
+    }
+
+    #[test]
+    fn errors_are_friendly_and_keep_the_game_going() {
+        let (solved, output) = play("place 99 0 0\nundo\nredo\nplace 0 9 9\nfly\nquit\nhint\n");
+        assert!(!solved);
+        assert!(output.contains("Oops: There is no piece 99"));
+        assert!(output.contains("Oops: There is nothing to undo"));
+        assert!(output.contains("Oops: There is nothing to redo"));
+        assert!(output.contains("Oops: Couldn't place piece 0 at (9, 9): Piece 0 doesn't fit!"));
+        assert!(output.contains("Oops: unknown command \"fly\""));
+        // quit 이후의 명령은 실행되지 않습니다.
+        assert!(!output.contains("Try piece"));
+    }
+}
@@ -1,7 +1,15 @@
 use anyhow::{Context, Result};
-use log::info;
-use puzzles::Puzzle;
+use clap::Parser;
+use cli::{Cli, Command};
+use game::Game;
+use log::{info, warn};
+use puzzles::{Catalog, ImagePuzzle, Progress, Puzzle};
 use std::fs::File;
+use std::io;
+use std::path::Path;
+
+mod cli;
+mod game;
 
 // 로그 라이브러리는 모든 기본 로거가 따르는 trait 을 통해 공통 인터페이스를 정의합니다.
 // 그래서 서로 다른 라이브러리와 응용 프로그램에 있는 로그가 모두 호환되는 거죠.
@@ -23,19 +31,173 @@ use std::fs::File;
 // 구조화 로깅, 컨텍스트, 스팬(spans), 비동기식 코드 또는 다중 스레드를 통한
 // 트레이싱(tracing) 요청 같은 고급 기능에 대해 알고싶다면, 좀 더 깊이 들어가 트레이싱 프레임워크를 공부해야 합니다.
 
-fn get_puzzle(filename: &str) -> Result<Puzzle> {
-    let fh = File::open(filename).with_context(|| format!("Failed to open {}", filename))?;
+fn get_puzzle(filename: &Path) -> Result<Puzzle> {
+    let fh =
+        File::open(filename).with_context(|| format!("Failed to open {}", filename.display()))?;
     let puzzle = Puzzle::from_file(fh).context("couldn't convert data into a puzzle")?;
     Ok(puzzle)
 }
 
+/// Generate, load or fall back to the default puzzle, as the options ask
+fn choose_puzzle(cli: &Cli, strict: bool) -> Result<Puzzle> {
+    if let Some((width, height)) = cli.generate {
+        return Puzzle::generate(width, height, cli.seed)
+            .with_context(|| format!("Couldn't generate a {}x{} puzzle", width, height));
+    }
+    match get_puzzle(&cli.file).context("Couldn't get the first puzzle") {
+        Ok(p) => Ok(p),
+        Err(e) if strict => Err(e),
+        Err(e) => {
+            // 에러를 그냥 삼키지 않고, 왜 기본 퍼즐을 쓰는지 남겨둡니다.
+            warn!("Using the default puzzle: {:#}", e);
+            Ok(Puzzle::new())
+        }
+    }
+}
+
 fn main() -> Result<()> {
-    // env_logger::init()을 호출해 초기화
-    env_logger::init();
-    let puzzle = match get_puzzle("puzzle.dat").context("Couldn't get the first puzzle") {
-        Ok(p) => p,
-        Err(_) => Puzzle::new(),
-    };
-    info!("Playing puzzle: {}", puzzle.name);
+    let cli = Cli::parse();
+    // env_logger::init() 대신 Builder 로 기본 로그 레벨을 정하고,
+    // RUST_LOG 환경 변수가 있으면 그 값이 덮어쓰도록 합니다.
+    env_logger::Builder::new()
+        .filter_level(cli.log_level)
+        .parse_default_env()
+        .init();
+
+    match cli.command.clone().unwrap_or(Command::Play) {
+        Command::Play => {
+            let puzzle = choose_puzzle(&cli, cli.strict)?;
+            info!(
+                "Playing puzzle: {} ({} x {} pieces)",
+                puzzle.name, puzzle.width, puzzle.height
+            );
+            // 진행 상황은 게임 안에서 `save` 명령으로 --file 에 저장합니다.
+            let mut game = Game::new(puzzle, &cli.file)?;
+            game.play(io::stdin().lock(), io::stdout())?;
+        }
+        Command::Info => {
+            let puzzle = choose_puzzle(&cli, cli.strict)?;
+            let count = |flats: usize| {
+                puzzle
+                    .pieces
+                    .iter()
+                    .filter(|piece| piece.flat_edges() == flats)
+                    .count()
+            };
+            println!("Name:     {}", puzzle.name);
+            println!("Size:     {} x {}", puzzle.width, puzzle.height);
+            println!("Pieces:   {}", puzzle.num_pieces);
+            println!("Corners:  {}", count(2));
+            println!("Border:   {}", count(1));
+            println!("Interior: {}", count(0));
+        }
+        Command::Replay => {
+            // 저장된 기록을 한 수씩 다시 두면서 보드를 보여줍니다.
+            let mut puzzle = choose_puzzle(&cli, true)?;
+            let history = std::mem::take(&mut puzzle.history);
+            let mut game = Game::new(puzzle, &cli.file)?;
+            println!("{}", game.render());
+            for (turn, mv) in history.into_iter().enumerate() {
+                game.apply(mv)
+                    .with_context(|| format!("Move {} can't be replayed", turn + 1))?;
+                println!("\n{}. {}", turn + 1, game::describe_move(mv));
+                println!("{}", game.render());
+            }
+        }
+        Command::Validate => {
+            // validate 는 항상 strict 입니다. 에러를 main 에서 돌려주면
+            // anyhow 가 "Caused by:" 아래에 원인을 전부 출력하고 0 이 아닌 코드로 끝납니다.
+            let puzzle = choose_puzzle(&cli, true)
+                .with_context(|| format!("{} is not a valid puzzle", cli.file.display()))?;
+            let solution = puzzle
+                .solve()
+                .with_context(|| format!("{} can't be solved", puzzle.name))?;
+            println!(
+                "{} is valid and solvable ({} nodes, {} backtracks)",
+                puzzle.name, solution.stats.nodes, solution.stats.backtracks
+            );
+            println!("Difficulty: {}", puzzle.difficulty_score());
+        }
+        Command::List {
+            dir,
+            min_pieces,
+            max_pieces,
+            name,
+            difficulty,
+            solved,
+            unsolved,
+        } => {
+            let catalog = Catalog::open(&dir)?;
+            let mut query = catalog.query();
+            if let Some(pieces) = min_pieces {
+                query = query.min_pieces(pieces);
+            }
+            if let Some(pieces) = max_pieces {
+                query = query.max_pieces(pieces);
+            }
+            if let Some(name) = &name {
+                query = query.name_contains(name);
+            }
+            if let Some(difficulty) = difficulty {
+                query = query.difficulty(difficulty);
+            }
+            if solved || unsolved {
+                query = query.solved(solved);
+            }
+            for (path, e) in catalog.skipped() {
+                eprintln!("Skipped {}: {}", path.display(), e.kind());
+            }
+            let entries = query.run();
+            if entries.is_empty() {
+                println!("No puzzles match in {}", dir.display());
+            } else {
+                print_catalog(&entries);
+            }
+        }
+        Command::Cut { image, pieces, out } => {
+            let (width, height) = pieces;
+            let cut = ImagePuzzle::open(&image, width, height, cli.seed)
+                .with_context(|| format!("Couldn't cut {} into pieces", image.display()))?;
+            let manifest = cut.export(&out)?;
+            println!(
+                "Cut {} into {} pieces in {}",
+                image.display(),
+                manifest.pieces.len(),
+                out.display()
+            );
+        }
+    }
     Ok(())
 }
+
+/// One line per puzzle, lined up in columns
+fn print_catalog(entries: &[&puzzles::CatalogEntry]) {
+    let width = entries
+        .iter()
+        .map(|entry| entry.name.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(4);
+    println!(
+        "{:<width$}  {:>6}  {:>9}  {:<10}  {:>7}  {:<12}  File",
+        "Name", "Pieces", "Size", "Difficulty", "Score", "Progress"
+    );
+    for entry in entries {
+        let progress = match entry.progress {
+            Progress::New => "new".to_string(),
+            Progress::Started { placed } => format!("{}/{} placed", placed, entry.num_pieces),
+            Progress::Solved => "solved".to_string(),
+        };
+        let file = entry.path.file_name().unwrap_or_default().to_string_lossy();
+        println!(
+            "{:<width$}  {:>6}  {:>9}  {:<10}  {:>7.0}  {:<12}  {}",
+            entry.name,
+            entry.num_pieces,
+            format!("{} x {}", entry.width, entry.height),
+            entry.difficulty,
+            entry.score.score(),
+            progress,
+            file
+        );
+    }
+}
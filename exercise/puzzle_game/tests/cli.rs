@@ -0,0 +1,122 @@
+use std::process::{Command, Output};
+
+fn run(dir: &std::path::Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_puzzle_game"))
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn validate_prints_the_whole_error_chain() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("broken.dat"), b"PUZL\x02\x05\x00Br").unwrap();
+
+    let output = run(dir.path(), &["validate", "--file", "broken.dat"]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(!output.status.success());
+    assert!(stderr.contains("broken.dat is not a valid puzzle"));
+    assert!(stderr.contains("Caused by:"));
+    assert!(stderr.contains("couldn't convert data into a puzzle"));
+    assert!(stderr.contains("Invalid puzzle data at byte 7"));
+    assert!(stderr.contains("The puzzle header is truncated"));
+}
+
+#[test]
+fn validate_accepts_a_good_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = run(
+        dir.path(),
+        &["--generate", "4x3", "--seed", "5", "validate"],
+    );
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("Generated 4x3 #5 is valid and solvable"));
+    assert!(stdout.contains("Difficulty: easy (score"));
+}
+
+#[test]
+fn strict_refuses_to_fall_back() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = run(dir.path(), &["info", "--strict"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("Failed to open puzzle.dat"));
+
+    // strict 가 아니면 기본 퍼즐로 넘어갑니다.
+    let output = run(dir.path(), &["info"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("Name:     Default"));
+    assert!(stdout.contains("Corners:  4"));
+}
+
+#[test]
+fn generates_puzzles_of_any_size() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = run(dir.path(), &["info", "--generate", "9x2", "--seed", "3"]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("Size:     9 x 2"));
+    assert!(stdout.contains("Pieces:   18"));
+
+    let output = run(dir.path(), &["info", "--generate", "nine"]);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn cut_exports_the_pieces() {
+    let dir = tempfile::tempdir().unwrap();
+    image::RgbaImage::from_pixel(80, 60, image::Rgba([10, 120, 40, 255]))
+        .save(dir.path().join("lake.png"))
+        .unwrap();
+
+    let output = run(
+        dir.path(),
+        &["cut", "lake.png", "-p", "4x3", "--out", "out"],
+    );
+    assert!(output.status.success());
+    assert!(String::from_utf8(output.stdout)
+        .unwrap()
+        .contains("Cut lake.png into 12 pieces in out"));
+    assert!(dir.path().join("out/manifest.json").exists());
+    assert!(dir.path().join("out/piece_11.png").exists());
+
+    let output = run(dir.path(), &["validate", "--file", "out/puzzle.dat"]);
+    assert!(output.status.success());
+}
+
+#[test]
+fn list_shows_matching_puzzles() {
+    let dir = tempfile::tempdir().unwrap();
+    for (file, width, height) in [("lake.dat", 25, 20), ("city.dat", 3, 2)] {
+        let puzzle = puzzles::Puzzle::generate(width, height, 0).unwrap();
+        puzzle.save(dir.path().join(file)).unwrap();
+    }
+    std::fs::write(dir.path().join("junk.dat"), b"nope").unwrap();
+
+    let output = run(dir.path(), &["list"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("Generated 25x20 #0"));
+    assert!(stdout.contains("Generated 3x2 #0"));
+    assert!(stdout.contains("25 x 20"));
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("Skipped"));
+
+    let output = run(
+        dir.path(),
+        &["list", "--min-pieces", "500", "--name", "GEN"],
+    );
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    // 탭 모양이 16 가지뿐인 500 조각 퍼즐이라 expert 입니다.
+    assert!(stdout.contains("expert"));
+    assert!(stdout.contains("Score"));
+    assert!(!stdout.contains("3x2"));
+
+    let output = run(dir.path(), &["list", "--solved"]);
+    assert!(String::from_utf8(output.stdout)
+        .unwrap()
+        .contains("No puzzles match"));
+}
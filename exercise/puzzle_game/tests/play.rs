@@ -0,0 +1,97 @@
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+// 실제 바이너리를 실행하고 stdin 으로 명령을 흘려 넣습니다.
+// puzzle.dat 을 건드리지 않도록 빈 임시 디렉터리에서 실행합니다.
+fn run(dir: &std::path::Path, script: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_puzzle_game"))
+        .current_dir(dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(script.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+// 기본 퍼즐은 풀린 순서 그대로라서 조각 번호 = y * 7 + x 입니다.
+fn solve_default() -> String {
+    let mut script = String::new();
+    for id in 0..42 {
+        script.push_str(&format!("place {} {} {}\n", id, id % 7, id / 7));
+    }
+    script
+}
+
+#[test]
+fn plays_a_full_game_to_completion() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = run(
+        dir.path(),
+        &format!("place 0 3 3\nundo\n{}", solve_default()),
+    );
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(output.status.success());
+    assert!(stdout.contains("Oops: Couldn't place piece 0 at (3, 3): Piece 0 doesn't fit!"));
+    assert!(stdout.contains("Solved! Well done."));
+}
+
+#[test]
+fn save_writes_puzzle_dat_and_quit_stops() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = run(dir.path(), "rotate 8\nsave\nquit\nplace 0 0 0\n");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(output.status.success());
+    assert!(stdout.contains("Piece 8 is turned 90 degrees"));
+    assert!(stdout.contains("Saved to puzzle.dat"));
+    assert!(!stdout.contains("Solved!"));
+    assert!(dir.path().join("puzzle.dat").exists());
+
+    // 저장한 퍼즐을 다시 불러오면 돌려 놓은 조각이 그대로 돌아가 있습니다.
+    let output = run(dir.path(), "rotate 8\nquit\n");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("Piece 8 is turned 180 degrees"));
+}
+
+#[test]
+fn saved_moves_come_back_and_can_be_undone() {
+    let dir = tempfile::tempdir().unwrap();
+    run(
+        dir.path(),
+        "place 0 0 0\nplace 1 1 0\nplace 2 2 0\nundo\nsave\nquit\n",
+    );
+
+    // 다시 시작하면 두 조각이 놓인 채로 시작하고, 저장 전의 수도 되돌릴 수 있습니다.
+    let output = run(dir.path(), "place 1 1 0\nundo\nundo\nundo\nquit\n");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("Oops: Piece 1 is already on the board at (1, 0)"));
+    assert!(stdout.contains("Undid: piece 1 placed at (1, 0)"));
+    assert!(stdout.contains("Undid: piece 0 placed at (0, 0)"));
+    assert!(stdout.contains("Oops: There is nothing to undo"));
+}
+
+#[test]
+fn replays_a_saved_game_move_by_move() {
+    let dir = tempfile::tempdir().unwrap();
+    run(
+        dir.path(),
+        "place 0 0 0\nplace 1 1 0\nundo\nredo\nsave\nquit\n",
+    );
+
+    let output = Command::new(env!("CARGO_BIN_EXE_puzzle_game"))
+        .current_dir(dir.path())
+        .arg("replay")
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(output.status.success());
+    assert!(stdout.contains("1. piece 0 placed at (0, 0)"));
+    assert!(stdout.contains("2. piece 1 placed at (1, 0)"));
+    assert!(!stdout.contains("3."));
+}
@@ -0,0 +1,295 @@
+use crate::piece::{Edge, Piece, Side};
+use crate::PuzzleError;
+
+/// Something done to the board, kept so it can be undone, redone or replayed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Move {
+    /// `piece` was put down at `(x, y)`
+    Place { piece: Piece, x: u16, y: u16 },
+    /// `piece` was taken back off `(x, y)`
+    Remove { piece: Piece, x: u16, y: u16 },
+}
+
+impl Move {
+    /// The move that takes this one back
+    pub fn inverse(self) -> Move {
+        match self {
+            Move::Place { piece, x, y } => Move::Remove { piece, x, y },
+            Move::Remove { piece, x, y } => Move::Place { piece, x, y },
+        }
+    }
+}
+
+/// Grid the pieces get placed on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Board {
+    width: u16,
+    height: u16,
+    cells: Vec<Option<Piece>>,
+    /// Moves made so far, oldest first
+    history: Vec<Move>,
+    /// Moves taken back with `undo`, most recent last
+    undone: Vec<Move>,
+}
+
+impl Board {
+    /// Make an empty board
+    pub fn new(width: u16, height: u16) -> Self {
+        Board {
+            width,
+            height,
+            cells: vec![None; width as usize * height as usize],
+            history: Vec::new(),
+            undone: Vec::new(),
+        }
+    }
+
+    pub fn width(&self) -> u16 {
+        self.width
+    }
+
+    pub fn height(&self) -> u16 {
+        self.height
+    }
+
+    /// The piece at `(x, y)`, if there is one
+    pub fn get(&self, x: u16, y: u16) -> Option<&Piece> {
+        self.index(x, y).and_then(|i| self.cells[i].as_ref())
+    }
+
+    /// Check whether `piece` could go at `(x, y)` without placing it
+    pub fn fits(&self, piece: &Piece, x: u16, y: u16) -> bool {
+        match self.index(x, y) {
+            Some(i) if self.cells[i].is_none() => {
+                Side::ALL
+                    .iter()
+                    .all(|&side| match self.neighbor(x, y, side) {
+                        // 보드 가장자리를 향한 변은 평평해야 합니다.
+                        None => piece.edge(side) == Edge::Flat,
+                        // 비어 있는 칸을 향한 변은 평평하지만 않으면 됩니다.
+                        Some(None) => piece.edge(side) != Edge::Flat,
+                        Some(Some(other)) => piece.edge(side).fits(other.edge(side.opposite())),
+                    })
+            }
+            _ => false,
+        }
+    }
+
+    /// Place a piece, or hand back `WontFit` if its edges don't match its neighbors
+    pub fn place(&mut self, piece: Piece, x: u16, y: u16) -> Result<(), PuzzleError> {
+        self.apply(Move::Place { piece, x, y })
+    }
+
+    /// Take the piece at `(x, y)` back off the board
+    pub fn remove(&mut self, x: u16, y: u16) -> Option<Piece> {
+        let piece = *self.get(x, y)?;
+        self.apply(Move::Remove { piece, x, y })
+            .expect("there is a piece to remove");
+        Some(piece)
+    }
+
+    /// Make a move and add it to the history
+    ///
+    /// A new move clears anything that could have been redone.
+    pub fn apply(&mut self, mv: Move) -> Result<(), PuzzleError> {
+        self.perform(mv)?;
+        self.history.push(mv);
+        self.undone.clear();
+        Ok(())
+    }
+
+    /// Take back the last move, returning it
+    pub fn undo(&mut self) -> Option<Move> {
+        let mv = self.history.pop()?;
+        self.perform(mv.inverse())
+            .expect("the board is just as the move left it");
+        self.undone.push(mv);
+        Some(mv)
+    }
+
+    /// Make the last undone move again, returning it
+    pub fn redo(&mut self) -> Option<Move> {
+        let mv = self.undone.pop()?;
+        self.perform(mv).expect("the board is just as undo left it");
+        self.history.push(mv);
+        Some(mv)
+    }
+
+    /// Every move made so far, oldest first
+    pub fn history(&self) -> &[Move] {
+        &self.history
+    }
+
+    /// Change the cells without touching the history
+    fn perform(&mut self, mv: Move) -> Result<(), PuzzleError> {
+        match mv {
+            Move::Place { piece, x, y } => {
+                if !self.fits(&piece, x, y) {
+                    return Err(PuzzleError::WontFit(piece.id));
+                }
+                let i = self.index(x, y).expect("fits() checked the bounds");
+                self.cells[i] = Some(piece);
+            }
+            Move::Remove { piece, x, y } => {
+                // 저장된 기록을 다시 재생할 때는 그 자리에 정말 그 조각이 있는지 확인해야 합니다.
+                let i = match self.index(x, y) {
+                    Some(i) if self.cells[i] == Some(piece) => i,
+                    _ => return Err(PuzzleError::MissingPiece),
+                };
+                self.cells[i] = None;
+            }
+        }
+        Ok(())
+    }
+
+    /// Where the piece with this id is, if it has been placed
+    pub fn position_of(&self, id: u16) -> Option<(u16, u16)> {
+        self.cells
+            .iter()
+            .position(|cell| cell.map(|piece| piece.id) == Some(id))
+            .map(|i| self.coords(i))
+    }
+
+    /// Number of pieces on the board
+    pub fn placed(&self) -> usize {
+        self.cells.iter().filter(|cell| cell.is_some()).count()
+    }
+
+    /// Every cell has a piece
+    pub fn is_complete(&self) -> bool {
+        self.cells.iter().all(Option::is_some)
+    }
+
+    fn index(&self, x: u16, y: u16) -> Option<usize> {
+        if x < self.width && y < self.height {
+            Some(y as usize * self.width as usize + x as usize)
+        } else {
+            None
+        }
+    }
+
+    fn coords(&self, i: usize) -> (u16, u16) {
+        (
+            (i % self.width as usize) as u16,
+            (i / self.width as usize) as u16,
+        )
+    }
+
+    /// `None` past the edge of the board, otherwise the (possibly empty) cell
+    fn neighbor(&self, x: u16, y: u16, side: Side) -> Option<Option<&Piece>> {
+        let (nx, ny) = match side {
+            Side::Top => (Some(x), y.checked_sub(1)),
+            Side::Right => (x.checked_add(1), Some(y)),
+            Side::Bottom => (Some(x), y.checked_add(1)),
+            Side::Left => (x.checked_sub(1), Some(y)),
+        };
+        let i = self.index(nx?, ny?)?;
+        Some(self.cells[i].as_ref())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::piece::cut;
+
+    fn pieces() -> Vec<Piece> {
+        cut(3, 2, |x, y, side| match side {
+            Side::Right => Edge::Tab(x as u8),
+            _ => Edge::Blank(y as u8 + 10),
+        })
+    }
+
+    #[test]
+    fn solved_layout_fits() {
+        let mut board = Board::new(3, 2);
+        for piece in pieces() {
+            let (x, y) = (piece.id % 3, piece.id / 3);
+            board.place(piece, x, y).unwrap();
+        }
+        assert!(board.is_complete());
+        assert_eq!(board.position_of(4), Some((1, 1)));
+    }
+
+    #[test]
+    fn mismatched_edges_wont_fit() {
+        let pieces = pieces();
+        let mut board = Board::new(3, 2);
+        board.place(pieces[0], 0, 0).unwrap();
+        // 모서리 조각은 다른 모서리 자리에 맞지 않습니다.
+        assert!(matches!(
+            board.place(pieces[2], 1, 0),
+            Err(PuzzleError::WontFit(2))
+        ));
+        // 같은 칸에 두 번 놓을 수는 없습니다.
+        assert!(matches!(
+            board.place(pieces[1], 0, 0),
+            Err(PuzzleError::WontFit(1))
+        ));
+        // 보드 밖도 안 됩니다.
+        assert!(matches!(
+            board.place(pieces[1], 3, 0),
+            Err(PuzzleError::WontFit(1))
+        ));
+        assert_eq!(board.placed(), 1);
+    }
+
+    #[test]
+    fn rotated_pieces_need_the_right_turn() {
+        let mut piece = pieces()[0];
+        let mut board = Board::new(3, 2);
+        piece.rotate();
+        assert!(!board.fits(&piece, 0, 0));
+        piece.rotate();
+        piece.rotate();
+        piece.rotate();
+        assert!(board.place(piece, 0, 0).is_ok());
+        assert_eq!(board.remove(0, 0), Some(piece));
+        assert_eq!(board.placed(), 0);
+    }
+
+    #[test]
+    fn undo_and_redo_walk_the_history() {
+        let pieces = pieces();
+        let mut board = Board::new(3, 2);
+        board.place(pieces[0], 0, 0).unwrap();
+        board.place(pieces[1], 1, 0).unwrap();
+        board.remove(0, 0);
+        assert_eq!(board.history().len(), 3);
+
+        assert_eq!(
+            board.undo(),
+            Some(Move::Remove {
+                piece: pieces[0],
+                x: 0,
+                y: 0
+            })
+        );
+        assert_eq!(board.get(0, 0), Some(&pieces[0]));
+        board.undo();
+        board.undo();
+        assert_eq!(board.undo(), None);
+        assert_eq!(board.placed(), 0);
+
+        board.redo();
+        board.redo();
+        assert_eq!(board.placed(), 2);
+        // 새로운 수를 두면 redo 할 수 있던 수는 사라집니다.
+        board.place(pieces[2], 2, 0).unwrap();
+        assert_eq!(board.redo(), None);
+        assert_eq!(board.history().len(), 3);
+    }
+
+    #[test]
+    fn replaying_a_bad_move_fails() {
+        let pieces = pieces();
+        let mut board = Board::new(3, 2);
+        let bogus = Move::Remove {
+            piece: pieces[0],
+            x: 0,
+            y: 0,
+        };
+        assert!(matches!(board.apply(bogus), Err(PuzzleError::MissingPiece)));
+        assert!(board.history().is_empty());
+    }
+}
@@ -0,0 +1,240 @@
+use crate::{Difficulty, DifficultyScore, Puzzle, PuzzleError};
+use log::{info, warn};
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+
+/// File extension of the puzzle files a catalog picks up
+pub const PUZZLE_EXTENSION: &str = "dat";
+
+/// Names are compared with this everywhere, so sorting, [`Catalog::get`] and
+/// [`Query::name_contains`] agree on what "ignoring case" means
+fn fold_case(name: &str) -> String {
+    // ASCII 만 보면 "Ölberg" 와 "ölberg" 가 다른 이름이 됩니다.
+    name.to_lowercase()
+}
+
+/// How far the player has got with a puzzle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Progress {
+    /// No moves saved yet
+    New,
+    /// Some pieces are on the board
+    Started {
+        placed: usize,
+    },
+    Solved,
+}
+
+/// What the catalog knows about one puzzle file without keeping the whole puzzle around
+#[derive(Debug, Clone)]
+pub struct CatalogEntry {
+    pub path: PathBuf,
+    pub name: String,
+    pub num_pieces: u32,
+    pub width: u16,
+    pub height: u16,
+    pub difficulty: Difficulty,
+    /// What [`CatalogEntry::difficulty`] is worked out from
+    pub score: DifficultyScore,
+    pub progress: Progress,
+}
+
+impl CatalogEntry {
+    fn new(path: PathBuf, puzzle: &Puzzle) -> Self {
+        // 저장된 수를 다시 둬 봐야 몇 조각이 놓였는지 알 수 있습니다.
+        let progress = match puzzle.resume() {
+            _ if puzzle.history.is_empty() => Progress::New,
+            Ok(board) if board.is_complete() => Progress::Solved,
+            Ok(board) => Progress::Started {
+                placed: board.placed(),
+            },
+            Err(e) => {
+                warn!("Saved moves in {} don't replay: {}", path.display(), e);
+                Progress::New
+            }
+        };
+        let score = puzzle.difficulty_score();
+        CatalogEntry {
+            path,
+            name: puzzle.name.clone(),
+            num_pieces: puzzle.num_pieces,
+            width: puzzle.width,
+            height: puzzle.height,
+            difficulty: score.difficulty(),
+            score,
+            progress,
+        }
+    }
+
+    /// Load the whole puzzle
+    pub fn load(&self) -> Result<Puzzle, PuzzleError> {
+        let fh = File::open(&self.path).map_err(PuzzleError::Read)?;
+        Puzzle::from_file(fh)
+    }
+}
+
+/// All the puzzles in one directory, sorted by name
+#[derive(Debug)]
+pub struct Catalog {
+    dir: PathBuf,
+    entries: Vec<CatalogEntry>,
+    /// Puzzle files that couldn't be read, and why
+    skipped: Vec<(PathBuf, PuzzleError)>,
+}
+
+impl Catalog {
+    /// Index every `.dat` file in `dir`
+    ///
+    /// Files that aren't valid puzzles are skipped rather than failing the
+    /// whole catalog; see [`Catalog::skipped`].
+    pub fn open<P: AsRef<Path>>(dir: P) -> Result<Self, PuzzleError> {
+        let dir = dir.as_ref();
+        let catalog_error = |source| PuzzleError::Catalog {
+            path: dir.to_path_buf(),
+            source,
+        };
+        let mut paths = Vec::new();
+        for entry in fs::read_dir(dir).map_err(catalog_error)? {
+            let path = entry.map_err(catalog_error)?.path();
+            if path.is_file() && path.extension().is_some_and(|ext| ext == PUZZLE_EXTENSION) {
+                paths.push(path);
+            }
+        }
+
+        let mut entries = Vec::with_capacity(paths.len());
+        let mut skipped = Vec::new();
+        for path in paths {
+            let loaded = File::open(&path)
+                .map_err(PuzzleError::Read)
+                .and_then(Puzzle::from_file);
+            match loaded {
+                Ok(puzzle) => entries.push(CatalogEntry::new(path, &puzzle)),
+                Err(e) => {
+                    warn!("Skipping {}: {}", path.display(), e);
+                    skipped.push((path, e));
+                }
+            }
+        }
+        // 이름이 같은 퍼즐이 있을 수 있어서 파일 경로로 한 번 더 정렬합니다.
+        entries.sort_by(|a, b| {
+            fold_case(&a.name)
+                .cmp(&fold_case(&b.name))
+                .then_with(|| a.path.cmp(&b.path))
+        });
+        info!(
+            "Catalog of {} has {} puzzles, skipped {} files",
+            dir.display(),
+            entries.len(),
+            skipped.len()
+        );
+        Ok(Catalog {
+            dir: dir.to_path_buf(),
+            entries,
+            skipped,
+        })
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Every puzzle, sorted by name
+    pub fn entries(&self) -> &[CatalogEntry] {
+        &self.entries
+    }
+
+    pub fn skipped(&self) -> &[(PathBuf, PuzzleError)] {
+        &self.skipped
+    }
+
+    /// Find a puzzle by name, ignoring case
+    pub fn get(&self, name: &str) -> Option<&CatalogEntry> {
+        let name = fold_case(name);
+        self.entries
+            .iter()
+            .find(|entry| fold_case(&entry.name) == name)
+    }
+
+    /// Start a search; with no conditions added it matches every puzzle
+    pub fn query(&self) -> Query<'_> {
+        Query {
+            catalog: self,
+            min_pieces: None,
+            max_pieces: None,
+            name: None,
+            difficulty: None,
+            solved: None,
+        }
+    }
+}
+
+/// Conditions on catalog entries, all of which have to hold
+///
+/// ```no_run
+/// # use puzzles::Catalog;
+/// let catalog = Catalog::open("puzzles")?;
+/// for entry in catalog.query().min_pieces(500).name_contains("lake").run() {
+///     println!("{}", entry.name);
+/// }
+/// # Ok::<(), puzzles::PuzzleError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Query<'a> {
+    catalog: &'a Catalog,
+    min_pieces: Option<u32>,
+    max_pieces: Option<u32>,
+    name: Option<String>,
+    difficulty: Option<Difficulty>,
+    solved: Option<bool>,
+}
+
+impl<'a> Query<'a> {
+    pub fn min_pieces(mut self, pieces: u32) -> Self {
+        self.min_pieces = Some(pieces);
+        self
+    }
+
+    pub fn max_pieces(mut self, pieces: u32) -> Self {
+        self.max_pieces = Some(pieces);
+        self
+    }
+
+    /// Name contains `text`, ignoring case
+    pub fn name_contains(mut self, text: &str) -> Self {
+        self.name = Some(fold_case(text));
+        self
+    }
+
+    pub fn difficulty(mut self, difficulty: Difficulty) -> Self {
+        self.difficulty = Some(difficulty);
+        self
+    }
+
+    /// Only solved puzzles, or only ones that still need work
+    pub fn solved(mut self, solved: bool) -> Self {
+        self.solved = Some(solved);
+        self
+    }
+
+    pub fn matches(&self, entry: &CatalogEntry) -> bool {
+        self.min_pieces.is_none_or(|min| entry.num_pieces >= min)
+            && self.max_pieces.is_none_or(|max| entry.num_pieces <= max)
+            && self
+                .name
+                .as_ref()
+                .is_none_or(|text| fold_case(&entry.name).contains(text))
+            && self.difficulty.is_none_or(|d| entry.difficulty == d)
+            && self
+                .solved
+                .is_none_or(|solved| (entry.progress == Progress::Solved) == solved)
+    }
+
+    /// The matching entries, in catalog order
+    pub fn run(&self) -> Vec<&'a CatalogEntry> {
+        self.catalog
+            .entries
+            .iter()
+            .filter(|entry| self.matches(entry))
+            .collect()
+    }
+}
@@ -0,0 +1,460 @@
+use crate::difficulty;
+use crate::piece::{Edge, Piece, Side};
+use crate::{DifficultyScore, Fractal, Puzzle, PuzzleError};
+use image::imageops::{self, FilterType};
+use image::{Pixel, RgbaImage};
+use log::info;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Name of the manifest written next to the piece images
+pub const MANIFEST_FILE: &str = "manifest.json";
+/// Name of the puzzle file written next to the piece images
+pub const PUZZLE_FILE: &str = "puzzle.dat";
+
+/// Smallest cell, in pixels, that still leaves room for the tabs
+const MIN_CELL: u32 = 8;
+
+/// A puzzle cut out of a picture
+#[derive(Debug, Clone)]
+pub struct ImagePuzzle {
+    /// The shuffled puzzle, as the game plays it
+    pub puzzle: Puzzle,
+    /// One picture per piece, by piece id
+    pub pieces: Vec<PieceImage>,
+    /// Size of one grid cell in pixels
+    pub cell_width: u32,
+    pub cell_height: u32,
+    /// How far tabs can stick out past the cell, in pixels
+    pub margin: u32,
+}
+
+/// The part of the picture one piece shows, unrotated
+#[derive(Debug, Clone)]
+pub struct PieceImage {
+    pub id: u16,
+    /// Where the top left corner of `image` goes in the solved picture
+    ///
+    /// Negative for pieces on the top or left border, because of the margin.
+    pub x: i32,
+    pub y: i32,
+    /// Pixels outside the piece's outline are fully transparent
+    pub image: RgbaImage,
+}
+
+/// What a graphical front end needs to lay out the exported pieces
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Manifest {
+    pub name: String,
+    /// Pieces across and down
+    pub width: u16,
+    pub height: u16,
+    pub cell_width: u32,
+    pub cell_height: u32,
+    pub margin: u32,
+    /// Puzzle file with the edges and the shuffled starting position
+    pub puzzle: String,
+    pub pieces: Vec<ManifestPiece>,
+}
+
+/// One exported piece image
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestPiece {
+    pub id: u16,
+    /// Image file, relative to the manifest
+    pub file: String,
+    /// Where the image goes in the solved picture, as in [`PieceImage`]
+    pub x: i32,
+    pub y: i32,
+    pub image_width: u32,
+    pub image_height: u32,
+}
+
+impl Manifest {
+    /// Read the manifest an export left in `dir`
+    pub fn load<P: AsRef<Path>>(dir: P) -> Result<Self, PuzzleError> {
+        let path = dir.as_ref().join(MANIFEST_FILE);
+        let data = fs::read(&path).map_err(|e| PuzzleError::Image {
+            path: path.clone(),
+            source: e.into(),
+        })?;
+        serde_json::from_slice(&data).map_err(|e| PuzzleError::Image {
+            path,
+            source: e.into(),
+        })
+    }
+}
+
+fn load_picture(path: &Path) -> Result<RgbaImage, PuzzleError> {
+    let picture = image::open(path).map_err(|e| PuzzleError::Image {
+        path: path.to_path_buf(),
+        source: e.into(),
+    })?;
+    Ok(picture.into_rgba8())
+}
+
+impl ImagePuzzle {
+    /// Load a picture and cut it into `width` x `height` pieces
+    ///
+    /// The edges come from [`Puzzle::generate`] with the same seed, and the
+    /// puzzle is named after the file.
+    pub fn open<P: AsRef<Path>>(
+        path: P,
+        width: u16,
+        height: u16,
+        seed: u64,
+    ) -> Result<Self, PuzzleError> {
+        let path = path.as_ref();
+        let picture = load_picture(path)?;
+        Self::cut_named(path, &picture, width, height, seed)
+    }
+
+    /// Like [`ImagePuzzle::open`], but first shrink the picture to fit in `max_width` x `max_height`
+    ///
+    /// The picture keeps its shape, and one that already fits is left as it is.
+    pub fn open_fitted<P: AsRef<Path>>(
+        path: P,
+        width: u16,
+        height: u16,
+        max_width: u32,
+        max_height: u32,
+        seed: u64,
+    ) -> Result<Self, PuzzleError> {
+        let path = path.as_ref();
+        let picture = load_picture(path)?;
+        // 사진은 보통 화면보다 훨씬 크니, 자르기 전에 줄여 둡니다.
+        let scale = (f64::from(max_width) / f64::from(picture.width()))
+            .min(f64::from(max_height) / f64::from(picture.height()));
+        if scale >= 1.0 {
+            return Self::cut_named(path, &picture, width, height, seed);
+        }
+        let fit = |side: u32| ((f64::from(side) * scale).round() as u32).max(1);
+        let picture = imageops::resize(
+            &picture,
+            fit(picture.width()),
+            fit(picture.height()),
+            FilterType::Triangle,
+        );
+        Self::cut_named(path, &picture, width, height, seed)
+    }
+
+    /// Cut a picture loaded from `path` and name the puzzle after the file
+    fn cut_named(
+        path: &Path,
+        picture: &RgbaImage,
+        width: u16,
+        height: u16,
+        seed: u64,
+    ) -> Result<Self, PuzzleError> {
+        let mut cut = Self::cut(picture, width, height, seed)?;
+        if let Some(stem) = path.file_stem() {
+            cut.puzzle.name = stem.to_string_lossy().into_owned();
+        }
+        Ok(cut)
+    }
+
+    /// Draw a fractal `pixel_width` x `pixel_height` and cut it up
+    pub fn from_fractal(
+        fractal: &Fractal,
+        pixel_width: u32,
+        pixel_height: u32,
+        width: u16,
+        height: u16,
+        seed: u64,
+    ) -> Result<Self, PuzzleError> {
+        let canvas = fractal
+            .render(pixel_width, pixel_height)
+            .map_err(|e| PuzzleError::Picture(e.into()))?;
+        let picture = RgbaImage::from_raw(pixel_width, pixel_height, canvas.into_pixels())
+            .expect("the canvas is exactly pixel_width x pixel_height");
+        let mut cut = Self::cut(&picture, width, height, seed)?;
+        cut.puzzle.name = match fractal {
+            Fractal::Mandelbrot { .. } => "Mandelbrot",
+            Fractal::Julia { .. } => "Julia",
+            Fractal::Koch { .. } => "Koch Snowflake",
+        }
+        .to_string();
+        Ok(cut)
+    }
+
+    /// Cut a picture that is already in memory
+    ///
+    /// Pixels that don't divide evenly into cells are cropped off the right
+    /// and bottom.
+    pub fn cut(
+        picture: &RgbaImage,
+        width: u16,
+        height: u16,
+        seed: u64,
+    ) -> Result<Self, PuzzleError> {
+        let puzzle = Puzzle::generate(width, height, seed)?;
+        let cell_width = picture.width() / u32::from(width);
+        let cell_height = picture.height() / u32::from(height);
+        if cell_width.min(cell_height) < MIN_CELL {
+            return Err(PuzzleError::ImageTooSmall {
+                image_width: picture.width(),
+                image_height: picture.height(),
+                width,
+                height,
+            });
+        }
+        let shape = Shape::new(cell_width, cell_height);
+
+        let mut pieces: Vec<PieceImage> = puzzle
+            .pieces
+            .iter()
+            .map(|piece| shape.cut_out(picture, piece, width))
+            .collect();
+        pieces.sort_by_key(|piece| piece.id);
+        info!(
+            "Cut a {}x{} picture into {} pieces of {}x{}",
+            picture.width(),
+            picture.height(),
+            pieces.len(),
+            cell_width,
+            cell_height
+        );
+        Ok(ImagePuzzle {
+            puzzle,
+            pieces,
+            cell_width,
+            cell_height,
+            margin: shape.margin,
+        })
+    }
+
+    /// Shannon entropy of the picture's brightness, in bits per pixel
+    pub fn entropy(&self) -> f64 {
+        let mut histogram = [0; 256];
+        for piece in &self.pieces {
+            // 조각 바깥의 투명한 픽셀은 그림이 아니니 세지 않습니다.
+            for pixel in piece.image.pixels().filter(|pixel| pixel[3] > 0) {
+                histogram[usize::from(pixel.to_luma()[0])] += 1;
+            }
+        }
+        difficulty::entropy(&histogram)
+    }
+
+    /// How hard the puzzle is, counting how much detail the picture has to go by
+    pub fn difficulty_score(&self) -> DifficultyScore {
+        self.puzzle.difficulty_score().with_entropy(self.entropy())
+    }
+
+    /// Write every piece as a PNG, the puzzle file and a manifest into `dir`
+    pub fn export<P: AsRef<Path>>(&self, dir: P) -> Result<Manifest, PuzzleError> {
+        let dir = dir.as_ref();
+        let export_error = |path: PathBuf| {
+            move |e: Box<dyn std::error::Error + Send + Sync>| PuzzleError::Export {
+                path,
+                source: e,
+            }
+        };
+        fs::create_dir_all(dir).map_err(|e| export_error(dir.to_path_buf())(e.into()))?;
+
+        let mut entries = Vec::with_capacity(self.pieces.len());
+        for piece in &self.pieces {
+            let file = format!("piece_{}.png", piece.id);
+            let path = dir.join(&file);
+            piece
+                .image
+                .save(&path)
+                .map_err(|e| export_error(path)(e.into()))?;
+            entries.push(ManifestPiece {
+                id: piece.id,
+                file,
+                x: piece.x,
+                y: piece.y,
+                image_width: piece.image.width(),
+                image_height: piece.image.height(),
+            });
+        }
+        self.puzzle.save(dir.join(PUZZLE_FILE))?;
+
+        let manifest = Manifest {
+            name: self.puzzle.name.clone(),
+            width: self.puzzle.width,
+            height: self.puzzle.height,
+            cell_width: self.cell_width,
+            cell_height: self.cell_height,
+            margin: self.margin,
+            puzzle: PUZZLE_FILE.to_string(),
+            pieces: entries,
+        };
+        let path = dir.join(MANIFEST_FILE);
+        let json = serde_json::to_vec_pretty(&manifest).expect("the manifest is plain data");
+        fs::write(&path, json).map_err(|e| export_error(path)(e.into()))?;
+        info!("Exported {} pieces to {}", self.pieces.len(), dir.display());
+        Ok(manifest)
+    }
+}
+
+/// Outline of the tabs and blanks, the same for every cell
+///
+/// A tab is a disc sitting just past the edge of the cell; the matching blank
+/// on the neighbor is the very same disc taken out of it. Both sides work in
+/// picture coordinates, so every pixel ends up in exactly one piece.
+struct Shape {
+    cell_width: u32,
+    cell_height: u32,
+    margin: u32,
+    radius: f32,
+    /// How far the middle of a tab is from the edge of the cell
+    reach: f32,
+}
+
+impl Shape {
+    fn new(cell_width: u32, cell_height: u32) -> Self {
+        let margin = cell_width.min(cell_height) / 4;
+        Shape {
+            cell_width,
+            cell_height,
+            margin,
+            radius: margin as f32 * 0.6,
+            reach: margin as f32 * 0.3,
+        }
+    }
+
+    fn cut_out(&self, picture: &RgbaImage, piece: &Piece, across: u16) -> PieceImage {
+        let column = u32::from(piece.id % across);
+        let row = u32::from(piece.id / across);
+        let left = (column * self.cell_width) as i32 - self.margin as i32;
+        let top = (row * self.cell_height) as i32 - self.margin as i32;
+        let size = |cell: u32| cell + 2 * self.margin;
+        let image = RgbaImage::from_fn(size(self.cell_width), size(self.cell_height), |x, y| {
+            let (px, py) = (left + x as i32, top + y as i32);
+            if self.covers(piece, column, row, px, py) {
+                *picture.get_pixel(px as u32, py as u32)
+            } else {
+                image::Rgba([0, 0, 0, 0])
+            }
+        });
+        PieceImage {
+            id: piece.id,
+            x: left,
+            y: top,
+            image,
+        }
+    }
+
+    /// Whether the pixel at `(px, py)` in the picture belongs to `piece`
+    fn covers(&self, piece: &Piece, column: u32, row: u32, px: i32, py: i32) -> bool {
+        let x0 = (column * self.cell_width) as f32;
+        let y0 = (row * self.cell_height) as f32;
+        let (x1, y1) = (x0 + self.cell_width as f32, y0 + self.cell_height as f32);
+        let (cx, cy) = (px as f32 + 0.5, py as f32 + 0.5);
+        let mut inside = cx > x0 && cx < x1 && cy > y0 && cy < y1;
+
+        for side in Side::ALL {
+            // 돌리기 전의 변을 봅니다. 그림은 맞춰진 방향 그대로 잘라냅니다.
+            let (shape, outward) = match piece.edges[side as usize] {
+                Edge::Flat => continue,
+                Edge::Tab(shape) => (shape, true),
+                Edge::Blank(shape) => (shape, false),
+            };
+            // 모양 번호에 따라 볼록한 부분이 변을 따라 조금씩 옮겨 갑니다.
+            let along = 0.5 + (f32::from(shape % 4) - 1.5) * 0.06;
+            let reach = if outward { self.reach } else { -self.reach };
+            let (kx, ky) = match side {
+                Side::Top => (x0 + along * self.cell_width as f32, y0 - reach),
+                Side::Bottom => (x0 + along * self.cell_width as f32, y1 + reach),
+                Side::Left => (x0 - reach, y0 + along * self.cell_height as f32),
+                Side::Right => (x1 + reach, y0 + along * self.cell_height as f32),
+            };
+            let in_knob = (cx - kx).powi(2) + (cy - ky).powi(2) < self.radius.powi(2);
+            if in_knob {
+                inside = outward;
+            }
+        }
+        inside
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn picture(width: u32, height: u32) -> RgbaImage {
+        RgbaImage::from_fn(width, height, |x, y| {
+            image::Rgba([x as u8, y as u8, (x * y) as u8, 255])
+        })
+    }
+
+    #[test]
+    fn every_pixel_lands_in_exactly_one_piece() {
+        let cut = ImagePuzzle::cut(&picture(130, 97), 4, 3, 5).unwrap();
+        // 남는 픽셀은 잘려나갑니다: 130 / 4 = 32, 97 / 3 = 32
+        assert_eq!((cut.cell_width, cut.cell_height), (32, 32));
+        let mut count = vec![0u8; 128 * 96];
+        for piece in &cut.pieces {
+            for (x, y, pixel) in piece.image.enumerate_pixels() {
+                if pixel[3] == 0 {
+                    continue;
+                }
+                let (px, py) = (piece.x + x as i32, piece.y + y as i32);
+                assert!((0..128).contains(&px) && (0..96).contains(&py));
+                count[py as usize * 128 + px as usize] += 1;
+                assert_eq!(pixel[0], px as u8);
+                assert_eq!(pixel[1], py as u8);
+            }
+        }
+        assert!(count.iter().all(|&n| n == 1));
+    }
+
+    #[test]
+    fn tabs_stick_out_and_blanks_cut_in() {
+        let cut = ImagePuzzle::cut(&picture(64, 64), 2, 2, 1).unwrap();
+        let margin = cut.margin;
+        for (piece, image) in cut
+            .puzzle
+            .pieces
+            .iter()
+            .map(|p| (p, &cut.pieces[p.id as usize]))
+        {
+            let right_middle = (
+                margin + cut.cell_width + margin / 2,
+                margin + cut.cell_height / 2,
+            );
+            let inside_right = (margin + cut.cell_width - 1, margin + cut.cell_height / 2);
+            let opaque = |(x, y): (u32, u32)| image.image.get_pixel(x, y)[3] == 255;
+            match piece.edges[Side::Right as usize] {
+                Edge::Flat => assert!(!opaque(right_middle)),
+                Edge::Tab(_) => assert!(opaque(right_middle)),
+                Edge::Blank(_) => assert!(!opaque(inside_right)),
+            }
+        }
+    }
+
+    #[test]
+    fn cuts_fractals_and_wraps_their_errors() {
+        let cut = ImagePuzzle::from_fractal(&Fractal::Koch { depth: 3 }, 120, 90, 4, 3, 2).unwrap();
+        assert_eq!(cut.puzzle.name, "Koch Snowflake");
+        assert_eq!(cut.pieces.len(), 12);
+
+        let err =
+            ImagePuzzle::from_fractal(&Fractal::Koch { depth: 20 }, 120, 90, 4, 3, 2).unwrap_err();
+        assert!(matches!(err, PuzzleError::Picture(_)));
+        let cause = std::error::Error::source(&err).unwrap();
+        assert!(cause.to_string().contains("snowflake"));
+    }
+
+    #[test]
+    fn plain_pictures_are_harder() {
+        let plain = RgbaImage::from_pixel(96, 64, image::Rgba([40, 120, 200, 255]));
+        let plain = ImagePuzzle::cut(&plain, 3, 2, 4).unwrap();
+        let busy = ImagePuzzle::cut(&picture(96, 64), 3, 2, 4).unwrap();
+        assert_eq!(plain.entropy(), 0.0);
+        assert!(busy.entropy() > 4.0);
+        let base = plain.puzzle.difficulty_score().score();
+        assert_eq!(plain.difficulty_score().score(), base * 1.5);
+        assert!(busy.difficulty_score().score() < plain.difficulty_score().score());
+    }
+
+    #[test]
+    fn refuses_cells_too_small_for_tabs() {
+        assert!(matches!(
+            ImagePuzzle::cut(&picture(30, 30), 5, 5, 0),
+            Err(PuzzleError::ImageTooSmall { .. })
+        ));
+    }
+}
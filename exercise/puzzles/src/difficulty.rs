@@ -0,0 +1,282 @@
+use crate::piece::Edge;
+use crate::Puzzle;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// How hard a puzzle is, going by its [`DifficultyScore`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Difficulty {
+    /// Score up to 500
+    Easy,
+    /// Score up to 2000
+    Medium,
+    /// Score up to 5000
+    Hard,
+    Expert,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 4] = [
+        Difficulty::Easy,
+        Difficulty::Medium,
+        Difficulty::Hard,
+        Difficulty::Expert,
+    ];
+
+    /// Difficulty of a puzzle with this [`DifficultyScore::score`]
+    pub fn for_score(score: f64) -> Difficulty {
+        match score {
+            s if s <= 500.0 => Difficulty::Easy,
+            s if s <= 2000.0 => Difficulty::Medium,
+            s if s <= 5000.0 => Difficulty::Hard,
+            _ => Difficulty::Expert,
+        }
+    }
+}
+
+/// What makes a puzzle hard, and a score that sums it up
+///
+/// The score is the number of pieces, times the average number of edges
+/// each tab or blank could be mistaken for, times a factor of 1 to 1.5
+/// for pictures with little detail to go by. Higher is harder.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DifficultyScore {
+    pub num_pieces: u32,
+    /// Average number of edges on other pieces that each tab or blank fits
+    ///
+    /// 1 means every seam has its own shape; it is never less than that.
+    pub ambiguity: f64,
+    /// Shannon entropy of the picture's brightness in bits per pixel, 0 to 8
+    ///
+    /// `None` when the puzzle has no picture.
+    pub entropy: Option<f64>,
+}
+
+impl DifficultyScore {
+    /// Rate a puzzle by its pieces alone
+    pub fn of(puzzle: &Puzzle) -> Self {
+        DifficultyScore {
+            num_pieces: puzzle.num_pieces,
+            ambiguity: ambiguity(&puzzle.pieces),
+            entropy: None,
+        }
+    }
+
+    /// The same rating, taking the picture into account too
+    pub fn with_entropy(self, entropy: f64) -> Self {
+        DifficultyScore {
+            entropy: Some(entropy.clamp(0.0, 8.0)),
+            ..self
+        }
+    }
+
+    pub fn score(&self) -> f64 {
+        // 그림이 단조로울수록(엔트로피가 낮을수록) 그림으로 조각을 찾기 어렵습니다.
+        let picture = self.entropy.map_or(1.0, |bits| 1.5 - bits / 16.0);
+        f64::from(self.num_pieces) * self.ambiguity * picture
+    }
+
+    pub fn difficulty(&self) -> Difficulty {
+        Difficulty::for_score(self.score())
+    }
+}
+
+impl fmt::Display for DifficultyScore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} (score {:.0}: {} pieces, {:.1} fits per edge",
+            self.difficulty(),
+            self.score(),
+            self.num_pieces,
+            self.ambiguity
+        )?;
+        if let Some(bits) = self.entropy {
+            write!(f, ", {:.1} bits per pixel", bits)?;
+        }
+        write!(f, ")")
+    }
+}
+
+/// Average number of edges on other pieces that each tab or blank fits
+fn ambiguity(pieces: &[crate::Piece]) -> f64 {
+    let mut counts: HashMap<Edge, usize> = HashMap::new();
+    for &edge in pieces.iter().flat_map(|piece| &piece.edges) {
+        *counts.entry(edge).or_default() += 1;
+    }
+    let mut fits = 0;
+    let mut edges = 0;
+    for piece in pieces {
+        for &edge in piece.edges.iter().filter(|&&edge| edge != Edge::Flat) {
+            // 같은 조각의 다른 변과는 이어 붙일 수 없으니 빼고 셉니다.
+            let own = piece
+                .edges
+                .iter()
+                .filter(|&&other| edge.fits(other))
+                .count();
+            fits += counts.get(&edge.complement()).map_or(0, |&n| n - own);
+            edges += 1;
+        }
+    }
+    if edges == 0 {
+        1.0
+    } else {
+        (fits as f64 / edges as f64).max(1.0)
+    }
+}
+
+/// Shannon entropy in bits of a histogram of byte values
+pub(crate) fn entropy(histogram: &[u64; 256]) -> f64 {
+    let total: u64 = histogram.iter().sum();
+    if total == 0 {
+        return 0.0;
+    }
+    histogram
+        .iter()
+        .filter(|&&n| n > 0)
+        .map(|&n| {
+            let p = n as f64 / total as f64;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Medium => "medium",
+            Difficulty::Hard => "hard",
+            Difficulty::Expert => "expert",
+        };
+        // `{:<8}` 같은 폭 지정이 먹도록 pad 를 씁니다.
+        f.pad(name)
+    }
+}
+
+impl FromStr for Difficulty {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Difficulty::ALL
+            .into_iter()
+            .find(|d| d.to_string().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| format!("{:?} is not one of easy, medium, hard or expert", s))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::generate::generate_with_shapes;
+
+    #[test]
+    fn higher_scores_are_harder() {
+        assert_eq!(Difficulty::for_score(42.0), Difficulty::Easy);
+        assert_eq!(Difficulty::for_score(2000.0), Difficulty::Medium);
+        assert_eq!(Difficulty::for_score(2000.5), Difficulty::Hard);
+        assert_eq!(Difficulty::for_score(50000.0), Difficulty::Expert);
+        assert!(Difficulty::Easy < Difficulty::Expert);
+    }
+
+    #[test]
+    fn unique_seams_are_not_ambiguous() {
+        let puzzle = Puzzle::try_from(crate::Profile::new("Unique", 4, 3)).unwrap();
+        let mut unique = puzzle.clone();
+        unique.pieces = crate::piece::cut(4, 3, |x, y, side| {
+            Edge::Tab((y * 8 + x * 2) as u8 + side as u8)
+        });
+        let score = DifficultyScore::of(&unique);
+        assert_eq!(score.ambiguity, 1.0);
+        assert_eq!(score.score(), 12.0);
+        assert!(DifficultyScore::of(&puzzle).ambiguity > 1.0);
+
+        // 그림이 단조로우면 더 어렵고, 그림이 없으면 점수에 영향이 없습니다.
+        let plain = score.with_entropy(0.0);
+        let busy = score.with_entropy(8.0);
+        assert_eq!(plain.score(), 18.0);
+        assert_eq!(busy.score(), score.score());
+        assert_eq!(
+            plain.to_string(),
+            "easy (score 18: 12 pieces, 1.0 fits per edge, 0.0 bits per pixel)"
+        );
+    }
+
+    #[test]
+    fn shows_up_in_puzzle_output() {
+        let puzzle = Puzzle::try_from(crate::Profile::forest_lake()).unwrap();
+        assert_eq!(
+            puzzle.to_string(),
+            "Forest Lake, 7 x 6, easy (score 374: 42 pieces, 8.9 fits per edge)"
+        );
+        assert!(format!("{:?}", puzzle).contains("difficulty: easy (score 374"));
+    }
+
+    #[test]
+    fn entropy_counts_bits_per_value() {
+        let mut histogram = [0; 256];
+        assert_eq!(entropy(&histogram), 0.0);
+        histogram[7] = 10;
+        assert_eq!(entropy(&histogram), 0.0);
+        histogram[200] = 10;
+        assert_eq!(entropy(&histogram), 1.0);
+        assert_eq!(entropy(&[1; 256]), 8.0);
+    }
+
+    /// Spearman's rank correlation, without ties
+    fn rank_correlation(a: &[f64], b: &[f64]) -> f64 {
+        let ranks = |values: &[f64]| {
+            let mut order: Vec<usize> = (0..values.len()).collect();
+            order.sort_by(|&i, &j| values[i].total_cmp(&values[j]));
+            let mut ranks = vec![0.0; values.len()];
+            for (rank, i) in order.into_iter().enumerate() {
+                ranks[i] = rank as f64;
+            }
+            ranks
+        };
+        let n = a.len() as f64;
+        let squares: f64 = ranks(a)
+            .iter()
+            .zip(ranks(b))
+            .map(|(x, y)| (x - y).powi(2))
+            .sum();
+        1.0 - 6.0 * squares / (n * (n * n - 1.0))
+    }
+
+    #[test]
+    fn scores_follow_solver_effort() {
+        // 크기와 탭 모양 수를 바꿔 가며 만든 퍼즐을 실제로 풀어 보고,
+        // 점수 순서가 solver 가 찾아본 노드 수 순서와 얼마나 맞는지 봅니다. (이 표본에서는 0.93)
+        let mut scores = Vec::new();
+        let mut nodes = Vec::new();
+        for (width, height, shapes) in [
+            (4, 3, 2),
+            (4, 3, 16),
+            (7, 6, 4),
+            (7, 6, 8),
+            (7, 6, 16),
+            (7, 6, 64),
+            (10, 10, 16),
+            (10, 10, 32),
+            (10, 10, 255),
+            (12, 10, 32),
+            (12, 10, 64),
+        ] {
+            let puzzle = generate_with_shapes(width, height, shapes, 1).unwrap();
+            scores.push(puzzle.difficulty_score().score());
+            nodes.push(puzzle.solve().unwrap().stats.nodes as f64);
+        }
+        let correlation = rank_correlation(&scores, &nodes);
+        assert!(correlation > 0.8, "rank correlation {:.2}", correlation);
+    }
+
+    #[test]
+    fn parses_what_it_displays() {
+        for difficulty in Difficulty::ALL {
+            assert_eq!(difficulty.to_string().parse(), Ok(difficulty));
+        }
+        assert_eq!("HARD".parse(), Ok(Difficulty::Hard));
+        assert!("impossible".parse::<Difficulty>().is_err());
+    }
+}
@@ -0,0 +1,517 @@
+// 퍼즐 파일 포맷 (모든 정수는 little endian)
+//
+// | 크기          | 내용                          |
+// |---------------|-------------------------------|
+// | 4             | magic `b"PUZL"`               |
+// | 1             | 포맷 버전                     |
+// | 2             | 이름 길이 (byte)              |
+// | 이름 길이     | 이름 (UTF-8)                  |
+// | 2             | 가로 조각 수                  |
+// | 2             | 세로 조각 수                  |
+// | 4             | 조각 개수 (가로 * 세로)       |
+// | 11 * 조각 개수| 조각 테이블                   |
+// | 4             | 기록된 수의 개수 (버전 3 부터)|
+// | 16 * 수 개수  | 수 기록 (버전 3 부터)         |
+// | 4             | CRC-32 체크섬 (버전 4 부터)   |
+//
+// 조각 하나는 11 byte 입니다.
+//
+// | 크기 | 내용                                              |
+// |------|---------------------------------------------------|
+// | 2    | 조각 번호                                         |
+// | 1    | 회전 (시계 방향 90도 횟수, 0 ~ 3)                 |
+// | 2 * 4| 위, 오른쪽, 아래, 왼쪽 변: 종류(0 평평, 1 볼록, 2 오목), 모양 |
+//
+// 수 하나는 16 byte 입니다: 종류(0 놓기, 1 빼기), x, y (각 2 byte), 그리고 조각 (11 byte)
+//
+// 버전 1 은 조각 번호만 저장했기 때문에 더 이상 읽지 않습니다.
+// 버전 2 는 수 기록이 없는 것만 빼면 버전 3 과 같아서 그대로 읽을 수 있습니다.
+// 버전 4 는 맨 끝에 그 앞의 모든 byte 에 대한 CRC-32 (zlib, PNG 와 같은 것) 를 붙입니다.
+
+use crate::board::Move;
+use crate::piece::{Edge, Piece, Rotation};
+use crate::{Puzzle, PuzzleError};
+use std::fmt;
+use std::io::Write;
+
+/// First bytes of every puzzle file
+pub const MAGIC: [u8; 4] = *b"PUZL";
+/// Version of the format written by this library
+pub const FORMAT_VERSION: u8 = 4;
+/// Oldest version this library can still read
+pub const OLDEST_VERSION: u8 = 2;
+
+/// Piece ids are `u16`, so no puzzle can have more pieces than this
+pub(crate) const MAX_PIECES: u32 = u16::MAX as u32 + 1;
+
+const PIECE_RECORD_LEN: usize = 11;
+const MOVE_RECORD_LEN: usize = 5 + PIECE_RECORD_LEN;
+const CHECKSUM_LEN: usize = 4;
+
+/// What [`salvage`] had to throw away to get a puzzle out of damaged data
+#[derive(Debug, Default)]
+pub struct DamageReport {
+    /// Everything that was wrong, each wrapped with the byte offset it was found at
+    pub problems: Vec<PuzzleError>,
+    /// Pieces that couldn't be read; the puzzle reports them as `MissingPiece`
+    ///
+    /// Only filled in when the whole piece table was there. A table that was
+    /// cut off is reported as `TruncatedPieceTable` instead of listing every id.
+    pub missing: Vec<u16>,
+    /// Saved moves that were dropped because they were unreadable or no longer replay
+    pub dropped_moves: u32,
+}
+
+impl DamageReport {
+    /// Nothing was wrong with the data
+    pub fn is_clean(&self) -> bool {
+        self.problems.is_empty() && self.missing.is_empty() && self.dropped_moves == 0
+    }
+}
+
+impl fmt::Display for DamageReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_clean() {
+            return write!(f, "No damage found");
+        }
+        for problem in &self.problems {
+            writeln!(f, "{}", problem.describe())?;
+        }
+        if !self.missing.is_empty() {
+            let ids: Vec<String> = self.missing.iter().map(u16::to_string).collect();
+            writeln!(f, "Missing pieces: {}", ids.join(" "))?;
+        }
+        write!(f, "Dropped moves: {}", self.dropped_moves)
+    }
+}
+
+/// Wrap a problem with where in the data it was found
+fn at(offset: usize) -> impl Fn(PuzzleError) -> PuzzleError {
+    move |source| PuzzleError::Invalid {
+        offset,
+        source: Box::new(source),
+    }
+}
+
+/// Everything before the piece table
+struct Header {
+    version: u8,
+    name: String,
+    width: u16,
+    height: u16,
+    num_pieces: u32,
+}
+
+/// Parse a whole puzzle file that is already in memory
+///
+/// Errors come back as [`PuzzleError::Invalid`], which says at which byte the
+/// problem was found and has the actual problem as its source.
+pub fn decode(data: &[u8]) -> Result<Puzzle, PuzzleError> {
+    let (body, checksum) = split_checksum(data)?;
+    if let Some(problem) = checksum {
+        return Err(problem);
+    }
+    let mut cursor = Cursor { data: body, pos: 0 };
+    let header = decode_header(&mut cursor)?;
+    let num_pieces = header.num_pieces;
+
+    // 조각 개수는 파일에 적힌 값이니 믿을 수 없습니다.
+    // 그래서 미리 큰 메모리를 잡지 않고, 남은 byte 수 만큼만 잡아둡니다.
+    let capacity = (num_pieces as usize).min(cursor.remaining() / PIECE_RECORD_LEN);
+    let mut pieces = Vec::with_capacity(capacity);
+    let mut seen = vec![false; num_pieces as usize];
+    for found in 0..num_pieces {
+        let offset = cursor.pos;
+        let record = cursor.take(PIECE_RECORD_LEN).map_err(|_| {
+            at(offset)(PuzzleError::TruncatedPieceTable {
+                expected: num_pieces,
+                found,
+            })
+        })?;
+        let piece = decode_piece(record).map_err(at(offset))?;
+        // 범위를 벗어났거나 이미 나온 번호의 조각
+        if !mark_seen(&mut seen, piece.id) {
+            return Err(at(offset)(PuzzleError::WontFit(piece.id)));
+        }
+        pieces.push(piece);
+    }
+
+    let mut history = Vec::new();
+    if header.version >= 3 {
+        let num_moves = cursor
+            .u32()
+            .map_err(|_| at(cursor.pos)(PuzzleError::MissingHistory))?;
+        history.reserve((num_moves as usize).min(cursor.remaining() / MOVE_RECORD_LEN));
+        for found in 0..num_moves {
+            let offset = cursor.pos;
+            let record = cursor.take(MOVE_RECORD_LEN).map_err(|_| {
+                at(offset)(PuzzleError::TruncatedHistory {
+                    expected: num_moves,
+                    found,
+                })
+            })?;
+            history.push(decode_move(record, found).map_err(at(offset))?);
+        }
+    }
+
+    Ok(Puzzle {
+        num_pieces,
+        name: header.name,
+        width: header.width,
+        height: header.height,
+        pieces,
+        history,
+    })
+}
+
+/// Get as much of a puzzle as possible out of damaged data
+///
+/// Only the header has to be intact. A checksum that doesn't match, piece
+/// records that can't be read and moves that no longer replay are dropped and
+/// listed in the [`DamageReport`] instead of failing the whole load, so the
+/// puzzle that comes back may be missing pieces.
+pub fn salvage(data: &[u8]) -> Result<(Puzzle, DamageReport), PuzzleError> {
+    let mut report = DamageReport::default();
+    let (body, checksum) = split_checksum(data)?;
+    report.problems.extend(checksum);
+    let mut cursor = Cursor { data: body, pos: 0 };
+    let header = decode_header(&mut cursor)?;
+    let num_pieces = header.num_pieces;
+
+    // 읽을 수 있는 조각만 건지고 나머지는 보고서에 적어 둡니다.
+    // 기록 하나의 길이는 정해져 있으니, 잘못된 기록을 건너뛰어도 다음 기록은 제자리에서 읽힙니다.
+    // 헤더의 조각 개수는 믿을 수 없으니, 남은 byte 에 들어 있는 기록까지만 봅니다.
+    let readable = (cursor.remaining() / PIECE_RECORD_LEN).min(num_pieces as usize);
+    let table_complete = readable == num_pieces as usize;
+    let mut found = vec![false; num_pieces as usize];
+    let mut pieces = Vec::with_capacity(readable);
+    for _ in 0..readable {
+        let offset = cursor.pos;
+        let Ok(record) = cursor.take(PIECE_RECORD_LEN) else {
+            break;
+        };
+        let piece = match decode_piece(record) {
+            Ok(piece) => piece,
+            Err(e) => {
+                report.problems.push(at(offset)(e));
+                continue;
+            }
+        };
+        // 범위를 벗어났거나 이미 나온 번호의 조각
+        if mark_seen(&mut found, piece.id) {
+            pieces.push(piece);
+        } else {
+            report
+                .problems
+                .push(at(offset)(PuzzleError::WontFit(piece.id)));
+        }
+    }
+    if table_complete {
+        report.missing = (0..=u16::MAX)
+            .zip(&found)
+            .filter(|&(_, &seen)| !seen)
+            .map(|(id, _)| id)
+            .collect();
+    } else {
+        report
+            .problems
+            .push(at(cursor.pos)(PuzzleError::TruncatedPieceTable {
+                expected: num_pieces,
+                found: readable as u32,
+            }));
+    }
+
+    let mut puzzle = Puzzle {
+        num_pieces,
+        name: header.name,
+        width: header.width,
+        height: header.height,
+        pieces,
+        history: Vec::new(),
+    };
+    if header.version < 3 || !table_complete {
+        return Ok((puzzle, report));
+    }
+
+    // 수 기록은 앞에서부터 다시 둬 보면서, 더 이상 둘 수 없는 곳에서 자릅니다.
+    let Ok(num_moves) = cursor.u32() else {
+        report
+            .problems
+            .push(at(cursor.pos)(PuzzleError::MissingHistory));
+        return Ok((puzzle, report));
+    };
+    let mut board = puzzle.board();
+    for index in 0..num_moves {
+        let offset = cursor.pos;
+        let mv = match cursor.take(MOVE_RECORD_LEN) {
+            Ok(record) => decode_move(record, index),
+            Err(Eof) => Err(PuzzleError::TruncatedHistory {
+                expected: num_moves,
+                found: index,
+            }),
+        };
+        let replayed = mv.and_then(|mv| {
+            let (Move::Place { piece, .. } | Move::Remove { piece, .. }) = mv;
+            if puzzle.piece(piece.id).is_none() {
+                return Err(PuzzleError::MissingPiece);
+            }
+            board.apply(mv).map(|()| mv)
+        });
+        match replayed {
+            Ok(mv) => puzzle.history.push(mv),
+            Err(e) => {
+                report.problems.push(at(offset)(e));
+                report.dropped_moves = num_moves - index;
+                break;
+            }
+        }
+    }
+    Ok((puzzle, report))
+}
+
+/// Tick off a piece id; false if it is out of range or was already ticked off
+fn mark_seen(seen: &mut [bool], id: u16) -> bool {
+    match seen.get_mut(id as usize) {
+        Some(seen) => !std::mem::replace(seen, true),
+        None => false,
+    }
+}
+
+/// Split off and check the checksum at the end of version 4 files
+///
+/// A mismatch is handed back next to the data instead of as an error, so
+/// [`salvage`] can carry on regardless.
+fn split_checksum(data: &[u8]) -> Result<(&[u8], Option<PuzzleError>), PuzzleError> {
+    let mut cursor = Cursor { data, pos: 0 };
+    if cursor.take(MAGIC.len()).ok() != Some(&MAGIC[..]) {
+        return Err(at(0)(PuzzleError::BadMagic));
+    }
+    let version = cursor
+        .u8()
+        .map_err(|_| at(cursor.pos)(PuzzleError::TruncatedHeader))?;
+    // 모르는 버전은 체크섬을 볼 것도 없이 헤더를 읽을 때 거부됩니다.
+    if !(4..=FORMAT_VERSION).contains(&version) {
+        return Ok((data, None));
+    }
+    let Some(body_len) = data
+        .len()
+        .checked_sub(CHECKSUM_LEN)
+        .filter(|&len| len >= cursor.pos)
+    else {
+        return Err(at(cursor.pos)(PuzzleError::TruncatedHeader));
+    };
+    let (body, stored) = data.split_at(body_len);
+    let expected = u32::from_le_bytes([stored[0], stored[1], stored[2], stored[3]]);
+    let found = checksum(body);
+    let problem = (expected != found)
+        .then(|| at(body_len)(PuzzleError::ChecksumMismatch { expected, found }));
+    Ok((body, problem))
+}
+
+fn decode_header(cursor: &mut Cursor) -> Result<Header, PuzzleError> {
+    // 에러가 난 위치는 읽고 있던 항목이 시작한 곳입니다.
+    // 조각 하나가 잘못됐다면 그 조각 기록의 첫 byte 를 가리킵니다.
+    if cursor.take(MAGIC.len()).ok() != Some(&MAGIC[..]) {
+        return Err(at(0)(PuzzleError::BadMagic));
+    }
+    let truncated = |cursor: &Cursor| at(cursor.pos)(PuzzleError::TruncatedHeader);
+    let version = cursor.u8().map_err(|_| truncated(cursor))?;
+    if !(OLDEST_VERSION..=FORMAT_VERSION).contains(&version) {
+        return Err(at(cursor.pos - 1)(PuzzleError::UnsupportedVersion(version)));
+    }
+    let name_len = cursor.u16().map_err(|_| truncated(cursor))?;
+    let name_start = cursor.pos;
+    let name = cursor
+        .take(name_len as usize)
+        .map_err(|_| truncated(cursor))?;
+    let name =
+        String::from_utf8(name.to_vec()).map_err(|e| at(name_start)(PuzzleError::BadName(e)))?;
+    let dimensions_start = cursor.pos;
+    let width = cursor.u16().map_err(|_| truncated(cursor))?;
+    let height = cursor.u16().map_err(|_| truncated(cursor))?;
+    let num_pieces = cursor.u32().map_err(|_| truncated(cursor))?;
+    // 가로 세로가 맞아도 조각 번호로 셀 수 없을 만큼 많으면 거부합니다.
+    if u32::from(width) * u32::from(height) != num_pieces || num_pieces > MAX_PIECES {
+        return Err(at(dimensions_start)(PuzzleError::BadDimensions {
+            width,
+            height,
+            num_pieces,
+        }));
+    }
+    Ok(Header {
+        version,
+        name,
+        width,
+        height,
+        num_pieces,
+    })
+}
+
+fn decode_move(record: &[u8], index: u32) -> Result<Move, PuzzleError> {
+    let x = u16::from_le_bytes([record[1], record[2]]);
+    let y = u16::from_le_bytes([record[3], record[4]]);
+    let piece = decode_piece(&record[5..])?;
+    match record[0] {
+        0 => Ok(Move::Place { piece, x, y }),
+        1 => Ok(Move::Remove { piece, x, y }),
+        _ => Err(PuzzleError::BadMove(index)),
+    }
+}
+
+fn encode_move(mv: &Move) -> [u8; MOVE_RECORD_LEN] {
+    let (kind, piece, x, y) = match *mv {
+        Move::Place { piece, x, y } => (0, piece, x, y),
+        Move::Remove { piece, x, y } => (1, piece, x, y),
+    };
+    let mut record = [0; MOVE_RECORD_LEN];
+    record[0] = kind;
+    record[1..3].copy_from_slice(&x.to_le_bytes());
+    record[3..5].copy_from_slice(&y.to_le_bytes());
+    record[5..].copy_from_slice(&encode_piece(&piece));
+    record
+}
+
+fn decode_piece(record: &[u8]) -> Result<Piece, PuzzleError> {
+    let id = u16::from_le_bytes([record[0], record[1]]);
+    if record[2] > 3 {
+        return Err(PuzzleError::BadPiece(id));
+    }
+    let mut edges = [Edge::Flat; 4];
+    for (edge, bytes) in edges.iter_mut().zip(record[3..].chunks(2)) {
+        *edge = match (bytes[0], bytes[1]) {
+            (0, _) => Edge::Flat,
+            (1, shape) => Edge::Tab(shape),
+            (2, shape) => Edge::Blank(shape),
+            _ => return Err(PuzzleError::BadPiece(id)),
+        };
+    }
+    Ok(Piece {
+        id,
+        edges,
+        rotation: Rotation::from_quarters(record[2]),
+    })
+}
+
+fn encode_piece(piece: &Piece) -> [u8; PIECE_RECORD_LEN] {
+    let mut record = [0; PIECE_RECORD_LEN];
+    record[0..2].copy_from_slice(&piece.id.to_le_bytes());
+    record[2] = piece.rotation.quarters();
+    for (bytes, edge) in record[3..].chunks_mut(2).zip(piece.edges) {
+        (bytes[0], bytes[1]) = match edge {
+            Edge::Flat => (0, 0),
+            Edge::Tab(shape) => (1, shape),
+            Edge::Blank(shape) => (2, shape),
+        };
+    }
+    record
+}
+
+/// Write a puzzle in the same format [`decode`] reads
+pub fn encode<W: Write>(puzzle: &Puzzle, mut writer: W) -> Result<(), PuzzleError> {
+    let name_len = u16::try_from(puzzle.name.len())
+        .map_err(|_| PuzzleError::NameTooLong(puzzle.name.len()))?;
+    // 읽을 때 거부할 파일은 처음부터 쓰지 않습니다.
+    // 조각 테이블에 남는 조각도, 번호가 범위를 벗어나거나 겹치는 조각처럼 맞지 않는 조각입니다.
+    if u32::from(puzzle.width) * u32::from(puzzle.height) != puzzle.num_pieces {
+        return Err(PuzzleError::BadDimensions {
+            width: puzzle.width,
+            height: puzzle.height,
+            num_pieces: puzzle.num_pieces,
+        });
+    }
+    if puzzle.pieces.len() < puzzle.num_pieces as usize {
+        return Err(PuzzleError::MissingPiece);
+    }
+    let mut seen = vec![false; puzzle.num_pieces as usize];
+    for (i, piece) in puzzle.pieces.iter().enumerate() {
+        if i >= puzzle.num_pieces as usize || !mark_seen(&mut seen, piece.id) {
+            return Err(PuzzleError::WontFit(piece.id));
+        }
+    }
+
+    // 메모리에 먼저 다 만들어 두고 한 번에 씁니다.
+    let mut data = Vec::new();
+    data.extend_from_slice(&MAGIC);
+    data.push(FORMAT_VERSION);
+    data.extend_from_slice(&name_len.to_le_bytes());
+    data.extend_from_slice(puzzle.name.as_bytes());
+    data.extend_from_slice(&puzzle.width.to_le_bytes());
+    data.extend_from_slice(&puzzle.height.to_le_bytes());
+    data.extend_from_slice(&puzzle.num_pieces.to_le_bytes());
+    for piece in &puzzle.pieces {
+        data.extend_from_slice(&encode_piece(piece));
+    }
+    let num_moves = u32::try_from(puzzle.history.len()).expect("fewer than 4 billion moves");
+    data.extend_from_slice(&num_moves.to_le_bytes());
+    for mv in &puzzle.history {
+        data.extend_from_slice(&encode_move(mv));
+    }
+    data.extend_from_slice(&checksum(&data).to_le_bytes());
+    writer.write_all(&data).map_err(PuzzleError::Write)
+}
+
+/// CRC-32 as used by zlib and PNG
+///
+/// Computed a bit at a time; puzzle files are small enough that a lookup
+/// table isn't worth it.
+fn checksum(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+/// Read-only view over the file contents that remembers how far we got
+struct Cursor<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+/// The cursor ran past the end of the data
+struct Eof;
+
+impl<'a> Cursor<'a> {
+    fn remaining(&self) -> usize {
+        self.data.len() - self.pos
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], Eof> {
+        if self.remaining() < len {
+            return Err(Eof);
+        }
+        let bytes = &self.data[self.pos..self.pos + len];
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8, Eof> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, Eof> {
+        let bytes = self.take(2)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn u32(&mut self) -> Result<u32, Eof> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn checksum_matches_crc32() {
+        // CRC-32 의 표준 검사 값
+        assert_eq!(checksum(b"123456789"), 0xCBF4_3926);
+        assert_eq!(checksum(b""), 0);
+    }
+}
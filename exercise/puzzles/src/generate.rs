@@ -0,0 +1,96 @@
+use crate::piece::{self, Edge, Rotation};
+use crate::{Puzzle, PuzzleError};
+use log::info;
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+
+/// Number of different tab shapes a seam can have
+///
+/// Fewer shapes means more pieces look alike, which makes the puzzle harder.
+const SHAPES: u8 = 16;
+
+/// Make a shuffled, solvable `width` x `height` puzzle
+///
+/// The same seed always gives the same puzzle. `ChaCha8Rng` is used instead of
+/// `StdRng` because its output is guaranteed not to change between `rand` versions.
+pub fn generate(width: u16, height: u16, seed: u64) -> Result<Puzzle, PuzzleError> {
+    generate_with_shapes(width, height, SHAPES, seed)
+}
+
+/// Like [`generate`], but seams pick from only `shapes` tab shapes
+pub(crate) fn generate_with_shapes(
+    width: u16,
+    height: u16,
+    shapes: u8,
+    seed: u64,
+) -> Result<Puzzle, PuzzleError> {
+    let num_pieces = u32::from(width) * u32::from(height);
+    // 조각 번호가 u16 이라 그보다 많은 조각은 만들 수 없습니다.
+    if num_pieces == 0 || num_pieces > u32::from(u16::MAX) + 1 {
+        return Err(PuzzleError::BadDimensions {
+            width,
+            height,
+            num_pieces,
+        });
+    }
+
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let mut pieces = piece::cut(width, height, |_, _, _| {
+        let shape = rng.gen_range(0..shapes);
+        if rng.gen() {
+            Edge::Tab(shape)
+        } else {
+            Edge::Blank(shape)
+        }
+    });
+    pieces.shuffle(&mut rng);
+    for piece in &mut pieces {
+        piece.rotation = Rotation::from_quarters(rng.gen_range(0..4));
+    }
+
+    let puzzle = Puzzle {
+        num_pieces,
+        name: format!("Generated {}x{} #{}", width, height, seed),
+        width,
+        height,
+        pieces,
+        history: Vec::new(),
+    };
+    info!("Generated puzzle {:?}", puzzle.name);
+    Ok(puzzle)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_puzzle() {
+        let a = generate(6, 4, 7).unwrap();
+        let b = generate(6, 4, 7).unwrap();
+        let c = generate(6, 4, 8).unwrap();
+        assert_eq!(a.pieces, b.pieces);
+        assert_ne!(a.pieces, c.pieces);
+    }
+
+    #[test]
+    fn generated_puzzles_are_solvable() {
+        for (width, height, seed) in [(1, 1, 0), (1, 5, 1), (3, 3, 2), (8, 5, 3)] {
+            let puzzle = generate(width, height, seed).unwrap();
+            assert_eq!(puzzle.pieces.len(), puzzle.num_pieces as usize);
+            assert!(puzzle.solve().unwrap().board.is_complete());
+        }
+    }
+
+    #[test]
+    fn rejects_empty_or_huge_puzzles() {
+        assert!(matches!(
+            generate(0, 5, 0),
+            Err(PuzzleError::BadDimensions { .. })
+        ));
+        assert!(matches!(
+            generate(300, 300, 0),
+            Err(PuzzleError::BadDimensions { .. })
+        ));
+    }
+}
@@ -1,16 +1,48 @@
-use log::{error, info};
-use std::fs::File;
+use log::{error, info, warn};
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
 use thiserror::Error;
 
+mod board;
+mod catalog;
+mod cutter;
+mod difficulty;
+pub mod format;
+mod generate;
+mod piece;
+mod profile;
+mod solver;
+
+pub use board::{Board, Move};
+pub use catalog::{Catalog, CatalogEntry, Progress, Query, PUZZLE_EXTENSION};
+pub use cutter::{ImagePuzzle, Manifest, ManifestPiece, PieceImage, MANIFEST_FILE, PUZZLE_FILE};
+pub use difficulty::{Difficulty, DifficultyScore};
+pub use format::DamageReport;
+// 그림을 고를 수 있도록 Fractal 은 내보내지만, FractalError 는 PuzzleError::Picture 로 감쌉니다.
+pub use fractal::Fractal;
+pub use piece::{Edge, Piece, Rotation, Side};
+pub use profile::Profile;
+pub use solver::{Solution, SolveStats};
+
 pub const PUZZLE_PIECES: u32 = 42;
 
-/// This is a Piece!
-#[derive(Debug, Clone)]
+/// This is a Puzzle!
+#[derive(Clone)]
 pub struct Puzzle {
     /// Number of piece
     pub num_pieces: u32,
     /// Descriptive name
     pub name: String,
+    /// Number of pieces across
+    pub width: u16,
+    /// Number of pieces down
+    pub height: u16,
+    /// Piece table, in the order the pieces are stored
+    pub pieces: Vec<Piece>,
+    /// Moves played so far, saved along with the puzzle
+    pub history: Vec<Move>,
 }
 
 impl Puzzle {
@@ -22,21 +54,180 @@ impl Puzzle {
     }
 
     /// Load a puzzle from a file
-    pub fn from_file(_fh: File) -> Result<Self, PuzzleError> {
-        error!("This file is missing a piece!");
-        Err(PuzzleError::MissingPiece)
+    ///
+    /// Files with a checksum are rejected with `ChecksumMismatch` if they
+    /// have been changed since they were saved.
+    pub fn from_file(fh: File) -> Result<Self, PuzzleError> {
+        Self::from_reader(fh)
+    }
+
+    /// Load a puzzle from anything that can be read, such as a file or a byte slice
+    pub fn from_reader<R: Read>(mut reader: R) -> Result<Self, PuzzleError> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data).map_err(PuzzleError::Read)?;
+        match format::decode(&data) {
+            Ok(puzzle) => {
+                info!(
+                    "Loaded puzzle {:?} with {} pieces",
+                    puzzle.name, puzzle.num_pieces
+                );
+                Ok(puzzle)
+            }
+            Err(e) => {
+                error!("This file is not a valid puzzle: {}", e.describe());
+                Err(e)
+            }
+        }
+    }
+
+    /// Load whatever can still be read from a damaged puzzle file
+    ///
+    /// Pieces that couldn't be read are left out of the puzzle and listed in
+    /// the report, so solving or saving it fails with `MissingPiece` until
+    /// they are put back. Only a file whose header is unreadable is an error.
+    pub fn repair_from_file(mut fh: File) -> Result<(Self, DamageReport), PuzzleError> {
+        let mut data = Vec::new();
+        fh.read_to_end(&mut data).map_err(PuzzleError::Read)?;
+        let (puzzle, report) = format::salvage(&data)?;
+        if report.is_clean() {
+            info!("Puzzle {:?} needed no repair", puzzle.name);
+        } else {
+            warn!(
+                "Repaired puzzle {:?}: {} problems, {} pieces missing, {} moves dropped",
+                puzzle.name,
+                report.problems.len(),
+                report.missing.len(),
+                report.dropped_moves
+            );
+        }
+        Ok((puzzle, report))
+    }
+
+    /// Make a random but solvable puzzle; the same seed always gives the same puzzle
+    pub fn generate(width: u16, height: u16, seed: u64) -> Result<Self, PuzzleError> {
+        generate::generate(width, height, seed)
+    }
+
+    /// An empty board the size of this puzzle
+    pub fn board(&self) -> Board {
+        Board::new(self.width, self.height)
+    }
+
+    /// Replay the saved moves onto an empty board, picking up where the player left off
+    ///
+    /// The returned board remembers the moves, so they can be undone again.
+    pub fn resume(&self) -> Result<Board, PuzzleError> {
+        let mut board = self.board();
+        for mv in &self.history {
+            board.apply(*mv)?;
+        }
+        Ok(board)
+    }
+
+    /// How hard the puzzle is
+    pub fn difficulty(&self) -> Difficulty {
+        self.difficulty_score().difficulty()
+    }
+
+    /// What makes the puzzle hard; see [`ImagePuzzle::difficulty_score`] to include the picture
+    pub fn difficulty_score(&self) -> DifficultyScore {
+        DifficultyScore::of(self)
+    }
+
+    /// Look a piece up by its id
+    pub fn piece(&self, id: u16) -> Option<&Piece> {
+        self.pieces.iter().find(|piece| piece.id == id)
+    }
+
+    /// Work out where every piece goes
+    pub fn solve(&self) -> Result<Solution, PuzzleError> {
+        solver::solve(self)
+    }
+
+    /// Write the puzzle in the format [`Puzzle::from_file`] reads
+    pub fn write_to<W: Write>(&self, writer: W) -> Result<(), PuzzleError> {
+        format::encode(self, writer)
+    }
+
+    /// Save the puzzle to `path` without ever leaving a half-written file behind
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), PuzzleError> {
+        let path = path.as_ref();
+        // 같은 디렉터리에 임시 파일을 먼저 쓰고 rename 합니다.
+        // rename 은 같은 파일 시스템 안에서 원자적이라, 도중에 프로그램이 죽어도
+        // 기존 파일은 그대로 남아 있습니다.
+        let mut tmp_name = path.as_os_str().to_owned();
+        tmp_name.push(".tmp");
+        let tmp_path = Path::new(&tmp_name);
+
+        let result = self.replace_via(tmp_path, path);
+        match result {
+            Ok(()) => info!("Saved puzzle {:?} to {}", self.name, path.display()),
+            Err(ref e) => {
+                error!("Couldn't save puzzle to {}: {}", path.display(), e);
+                let _ = fs::remove_file(tmp_path);
+            }
+        }
+        result
+    }
+
+    fn replace_via(&self, tmp_path: &Path, path: &Path) -> Result<(), PuzzleError> {
+        let save_error = |source| PuzzleError::Save {
+            path: path.to_path_buf(),
+            source,
+        };
+        let mut writer = BufWriter::new(File::create(tmp_path).map_err(save_error)?);
+        self.write_to(&mut writer)?;
+        let fh = writer
+            .into_inner()
+            .map_err(|e| PuzzleError::Write(e.into_error()))?;
+        fh.sync_all().map_err(save_error)?;
+        fs::rename(tmp_path, path).map_err(save_error)?;
+        Ok(())
     }
 }
 
 impl Default for Puzzle {
+    /// A solved puzzle made from [`Profile::current`]
     fn default() -> Self {
-        Puzzle {
-            num_pieces: PUZZLE_PIECES,
-            name: "Default".to_string(),
-        }
+        Puzzle::try_from(Profile::current())
+            .expect("profiles are checked before they become the default")
+    }
+}
+
+// 조각 목록은 길어서, 직접 구현해 난이도를 앞쪽에 함께 보여 줍니다.
+impl fmt::Debug for Puzzle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Puzzle")
+            .field("num_pieces", &self.num_pieces)
+            .field("name", &self.name)
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .field("difficulty", &format_args!("{}", self.difficulty_score()))
+            .field("pieces", &self.pieces)
+            .field("history", &self.history)
+            .finish()
+    }
+}
+
+/// Name, size and difficulty, e.g. `Forest Lake, 7 x 6, easy (score 374: 42 pieces, 8.9 fits per edge)`
+impl fmt::Display for Puzzle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}, {} x {}, {}",
+            self.name,
+            self.width,
+            self.height,
+            self.difficulty_score()
+        )
     }
 }
 
+/// Everything that can go wrong with a puzzle
+///
+/// Problems found while reading puzzle data are wrapped in
+/// [`PuzzleError::Invalid`], so the error chain says where the data went bad
+/// before saying what was wrong with it.
 #[derive(Debug, Error)]
 #[non_exhaustive]
 pub enum PuzzleError {
@@ -44,4 +235,107 @@ pub enum PuzzleError {
     MissingPiece,
     #[error("Piece {0} doesn't fit!")]
     WontFit(u16),
+    #[error("Not a puzzle file (bad magic number)")]
+    BadMagic,
+    #[error("Unsupported puzzle format version {0}")]
+    UnsupportedVersion(u8),
+    #[error("The puzzle header is truncated")]
+    TruncatedHeader,
+    #[error("The piece table is truncated: expected {expected} pieces, found {found}")]
+    TruncatedPieceTable { expected: u32, found: u32 },
+    #[error("A {width} x {height} puzzle can't have {num_pieces} pieces")]
+    BadDimensions {
+        width: u16,
+        height: u16,
+        num_pieces: u32,
+    },
+    #[error("Can't make {0:?} the default puzzle profile: the default is already set")]
+    DefaultProfileSet(String),
+    #[error("{0} pieces can't be laid out in a grid")]
+    NoLayout(u32),
+    #[error("Piece {0} has a malformed record")]
+    BadPiece(u16),
+    #[error("The move history is missing")]
+    MissingHistory,
+    #[error("The move history is truncated: expected {expected} moves, found {found}")]
+    TruncatedHistory { expected: u32, found: u32 },
+    #[error("Move {0} in the history is malformed")]
+    BadMove(u32),
+    #[error(
+        "Checksum mismatch: the file says {expected:#010x} but the data adds up to {found:#010x}"
+    )]
+    ChecksumMismatch { expected: u32, found: u32 },
+    #[error("The puzzle name is not valid UTF-8")]
+    BadName(#[source] std::string::FromUtf8Error),
+    #[error("The puzzle name is too long ({0} bytes)")]
+    NameTooLong(usize),
+    #[error(
+        "A {image_width} x {image_height} image is too small to cut into {width} x {height} pieces"
+    )]
+    ImageTooSmall {
+        image_width: u32,
+        image_height: u32,
+        width: u16,
+        height: u16,
+    },
+    // 이미지 라이브러리의 에러 타입은 그대로 내보내지 않고 원인으로만 달아 둡니다.
+    #[error("Couldn't read {}", path.display())]
+    Image {
+        path: PathBuf,
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    #[error("Couldn't draw the picture for the puzzle")]
+    Picture(#[source] Box<dyn std::error::Error + Send + Sync>),
+    #[error("Couldn't read the catalog directory {}", path.display())]
+    Catalog {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("Couldn't export the puzzle to {}", path.display())]
+    Export {
+        path: PathBuf,
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    #[error("Invalid puzzle data at byte {offset}")]
+    Invalid {
+        /// Where the bad part starts, counting from the start of the data
+        offset: usize,
+        source: Box<PuzzleError>,
+    },
+    #[error("Couldn't read the puzzle data")]
+    Read(#[source] std::io::Error),
+    #[error("Couldn't write the puzzle data")]
+    Write(#[source] std::io::Error),
+    #[error("Couldn't save the puzzle to {}", path.display())]
+    Save {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+}
+
+impl PuzzleError {
+    /// Byte offset in the puzzle data where the problem was found, if it came from reading one
+    pub fn offset(&self) -> Option<usize> {
+        match self {
+            PuzzleError::Invalid { offset, .. } => Some(*offset),
+            _ => None,
+        }
+    }
+
+    /// The problem itself, without the location wrapped around it
+    pub fn kind(&self) -> &PuzzleError {
+        match self {
+            PuzzleError::Invalid { source, .. } => source.kind(),
+            other => other,
+        }
+    }
+
+    /// The error followed by the problem it wraps, if any, so nothing is said twice
+    pub(crate) fn describe(&self) -> String {
+        // 위치로 감싸지 않은 에러는 kind() 가 자기 자신이라 한 번만 씁니다.
+        match self.offset() {
+            Some(_) => format!("{}: {}", self, self.kind()),
+            None => self.to_string(),
+        }
+    }
 }
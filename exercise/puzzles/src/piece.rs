@@ -0,0 +1,195 @@
+/// One side of a piece, as seen when the piece is not rotated
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Side {
+    Top,
+    Right,
+    Bottom,
+    Left,
+}
+
+impl Side {
+    /// All sides, clockwise from the top
+    pub const ALL: [Side; 4] = [Side::Top, Side::Right, Side::Bottom, Side::Left];
+
+    /// The side facing this one on the neighboring piece
+    pub fn opposite(self) -> Side {
+        Side::ALL[(self as usize + 2) % 4]
+    }
+}
+
+/// Profile of one edge of a piece
+///
+/// Tabs stick out and blanks are holes. A tab only fits a blank with the same
+/// shape number, and flat edges only belong on the border of the board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Edge {
+    Flat,
+    Tab(u8),
+    Blank(u8),
+}
+
+impl Edge {
+    /// Can this edge sit next to `other`?
+    pub fn fits(self, other: Edge) -> bool {
+        match (self, other) {
+            (Edge::Tab(a), Edge::Blank(b)) | (Edge::Blank(a), Edge::Tab(b)) => a == b,
+            _ => false,
+        }
+    }
+
+    /// The edge that fits this one
+    pub fn complement(self) -> Edge {
+        match self {
+            Edge::Flat => Edge::Flat,
+            Edge::Tab(shape) => Edge::Blank(shape),
+            Edge::Blank(shape) => Edge::Tab(shape),
+        }
+    }
+}
+
+/// How far a piece has been turned clockwise
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Rotation {
+    #[default]
+    R0,
+    R90,
+    R180,
+    R270,
+}
+
+impl Rotation {
+    /// All rotations, in clockwise order
+    pub const ALL: [Rotation; 4] = [Rotation::R0, Rotation::R90, Rotation::R180, Rotation::R270];
+
+    /// Turn another quarter clockwise
+    pub fn turn(self) -> Rotation {
+        Rotation::ALL[(self as usize + 1) % 4]
+    }
+
+    /// Number of quarter turns, 0 to 3
+    pub fn quarters(self) -> u8 {
+        self as u8
+    }
+
+    /// Rotation for a number of quarter turns, wrapping around after 4
+    pub fn from_quarters(quarters: u8) -> Rotation {
+        Rotation::ALL[quarters as usize % 4]
+    }
+}
+
+/// This is a Piece!
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Piece {
+    /// Piece number, unique within its puzzle
+    pub id: u16,
+    /// Edge profiles in `Side::ALL` order, before rotation
+    pub edges: [Edge; 4],
+    /// How the piece is currently turned
+    pub rotation: Rotation,
+}
+
+impl Piece {
+    /// Make an unrotated piece
+    pub fn new(id: u16, edges: [Edge; 4]) -> Self {
+        Piece {
+            id,
+            edges,
+            rotation: Rotation::R0,
+        }
+    }
+
+    /// The edge that currently faces `side`, taking rotation into account
+    pub fn edge(&self, side: Side) -> Edge {
+        // 시계 방향으로 한 번 돌리면 원래 왼쪽(Left)이던 변이 위(Top)를 향하게 됩니다.
+        let turns = self.rotation.quarters() as usize;
+        self.edges[(side as usize + 4 - turns) % 4]
+    }
+
+    /// Turn the piece a quarter clockwise
+    pub fn rotate(&mut self) {
+        self.rotation = self.rotation.turn();
+    }
+
+    /// Number of flat edges: 2 for corners, 1 for border pieces, 0 for the interior
+    pub fn flat_edges(&self) -> usize {
+        self.edges
+            .iter()
+            .filter(|&&edge| edge == Edge::Flat)
+            .count()
+    }
+}
+
+/// Cut a `width` x `height` grid into interlocking pieces, in solved order
+///
+/// `seam` picks the edge on the right or bottom side of the piece at `(x, y)`;
+/// the neighbor gets the complement so the two always fit.
+pub(crate) fn cut<F>(width: u16, height: u16, mut seam: F) -> Vec<Piece>
+where
+    F: FnMut(u16, u16, Side) -> Edge,
+{
+    let mut pieces: Vec<Piece> = Vec::with_capacity(width as usize * height as usize);
+    for y in 0..height {
+        for x in 0..width {
+            let index = y as usize * width as usize + x as usize;
+            let top = match y {
+                0 => Edge::Flat,
+                _ => pieces[index - width as usize].edges[Side::Bottom as usize].complement(),
+            };
+            let left = match x {
+                0 => Edge::Flat,
+                _ => pieces[index - 1].edges[Side::Right as usize].complement(),
+            };
+            let right = if x + 1 == width {
+                Edge::Flat
+            } else {
+                seam(x, y, Side::Right)
+            };
+            let bottom = if y + 1 == height {
+                Edge::Flat
+            } else {
+                seam(x, y, Side::Bottom)
+            };
+            pieces.push(Piece::new(index as u16, [top, right, bottom, left]));
+        }
+    }
+    pieces
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn tabs_fit_matching_blanks_only() {
+        assert!(Edge::Tab(3).fits(Edge::Blank(3)));
+        assert!(Edge::Blank(3).fits(Edge::Tab(3)));
+        assert!(!Edge::Tab(3).fits(Edge::Blank(4)));
+        assert!(!Edge::Tab(3).fits(Edge::Tab(3)));
+        assert!(!Edge::Flat.fits(Edge::Flat));
+    }
+
+    #[test]
+    fn rotation_moves_edges_clockwise() {
+        let mut piece = Piece::new(0, [Edge::Flat, Edge::Tab(1), Edge::Blank(2), Edge::Tab(3)]);
+        piece.rotate();
+        assert_eq!(piece.edge(Side::Top), Edge::Tab(3));
+        assert_eq!(piece.edge(Side::Right), Edge::Flat);
+        assert_eq!(piece.edge(Side::Bottom), Edge::Tab(1));
+        assert_eq!(piece.edge(Side::Left), Edge::Blank(2));
+        piece.rotate();
+        piece.rotate();
+        piece.rotate();
+        assert_eq!(piece.rotation, Rotation::R0);
+        assert_eq!(piece.edge(Side::Top), Edge::Flat);
+    }
+
+    #[test]
+    fn cut_pieces_interlock() {
+        let pieces = cut(3, 2, |x, y, _| Edge::Tab((x + y) as u8));
+        assert_eq!(pieces.len(), 6);
+        assert_eq!(pieces[0].flat_edges(), 2);
+        assert_eq!(pieces[1].flat_edges(), 1);
+        assert!(pieces[0].edge(Side::Right).fits(pieces[1].edge(Side::Left)));
+        assert!(pieces[1].edge(Side::Bottom).fits(pieces[4].edge(Side::Top)));
+    }
+}
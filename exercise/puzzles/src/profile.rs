@@ -0,0 +1,210 @@
+use crate::format::MAX_PIECES;
+use crate::piece::{self, Edge, Piece};
+use crate::{Puzzle, PuzzleError};
+use log::info;
+use std::sync::OnceLock;
+
+/// Default layout: 7 x 6 = `PUZZLE_PIECES`
+const DEFAULT_WIDTH: u16 = 7;
+const DEFAULT_HEIGHT: u16 = 6;
+
+/// Set once with [`Profile::set_default`], or [`Profile::standard`] from the first time it is used
+static DEFAULT_PROFILE: OnceLock<Profile> = OnceLock::new();
+
+/// The name and layout a new puzzle is made from
+///
+/// This is the canonical description of a puzzle for crates that only care
+/// about what it is called and how many pieces it has. A full [`Puzzle`] can
+/// be made from any valid profile, and every puzzle has one.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Profile {
+    pub name: String,
+    /// Number of pieces across
+    pub width: u16,
+    /// Number of pieces down
+    pub height: u16,
+}
+
+impl Profile {
+    pub fn new(name: impl Into<String>, width: u16, height: u16) -> Self {
+        Self {
+            name: name.into(),
+            width,
+            height,
+        }
+    }
+
+    /// "Default", 7 x 6
+    pub fn standard() -> Self {
+        Self::new("Default", DEFAULT_WIDTH, DEFAULT_HEIGHT)
+    }
+
+    /// "Forest Lake", 7 x 6
+    pub fn forest_lake() -> Self {
+        Self::new("Forest Lake", DEFAULT_WIDTH, DEFAULT_HEIGHT)
+    }
+
+    /// The most nearly square layout of `num_pieces` pieces, never taller than wide
+    pub fn for_pieces(name: impl Into<String>, num_pieces: u32) -> Result<Self, PuzzleError> {
+        // 제곱근을 넘지 않는 약수 중 가장 큰 것을 높이로 하면 가장 정사각형에 가깝습니다.
+        let height = (1..)
+            .take_while(|&h| h <= num_pieces / h)
+            .filter(|&h| num_pieces.is_multiple_of(h))
+            .last()
+            .unwrap_or(0);
+        let width = num_pieces.checked_div(height).unwrap_or(0);
+        // 높이는 제곱근을 넘지 않으니, 넘칠 수 있는 건 폭뿐입니다.
+        let (Ok(width), Ok(height)) = (u16::try_from(width), u16::try_from(height)) else {
+            return Err(PuzzleError::NoLayout(num_pieces));
+        };
+        let profile = Self::new(name, width, height);
+        profile.check()?;
+        Ok(profile)
+    }
+
+    pub fn num_pieces(&self) -> u32 {
+        u32::from(self.width) * u32::from(self.height)
+    }
+
+    /// The profile `Puzzle::default()` is made from
+    ///
+    /// Once this has been asked for, the default can no longer be changed.
+    pub fn current() -> Self {
+        DEFAULT_PROFILE.get_or_init(Self::standard).clone()
+    }
+
+    /// Make `Puzzle::default()` use `profile` for the rest of the program
+    ///
+    /// Meant to be called once at the start of `main`. It fails if a default
+    /// was already set, or already used, so it can't change under anyone's feet.
+    /// Code that needs some other layout should pass its own profile to
+    /// `Puzzle::try_from` instead.
+    pub fn set_default(profile: Profile) -> Result<(), PuzzleError> {
+        profile.check()?;
+        // 이미 누군가 기본값을 썼다면 그 사이에 바뀌면 안 되니 거부합니다.
+        DEFAULT_PROFILE
+            .set(profile)
+            .map_err(|profile| PuzzleError::DefaultProfileSet(profile.name))?;
+        info!("Default puzzle profile is {:?}", Self::current());
+        Ok(())
+    }
+
+    fn check(&self) -> Result<(), PuzzleError> {
+        let num_pieces = self.num_pieces();
+        // 조각 번호가 u16 이라 그보다 많은 조각은 만들 수 없습니다.
+        if num_pieces == 0 || num_pieces > MAX_PIECES {
+            return Err(PuzzleError::BadDimensions {
+                width: self.width,
+                height: self.height,
+                num_pieces,
+            });
+        }
+        Ok(())
+    }
+
+    /// Solved pieces with a fixed pattern of tabs and blanks
+    fn cut(&self) -> Vec<Piece> {
+        piece::cut(self.width, self.height, |x, y, side| {
+            // 모양 번호가 이웃끼리 겹치지 않도록 위치에 따라 바꿔 줍니다.
+            // 한 줄짜리 긴 퍼즐에서는 x * 3 이 u16 을 넘으니 u32 로 셉니다.
+            let (x, y) = (u32::from(x), u32::from(y));
+            let shape = ((x * 3 + y * 5 + side as u32) % 8) as u8;
+            if (x + y) % 2 == 0 {
+                Edge::Tab(shape)
+            } else {
+                Edge::Blank(shape)
+            }
+        })
+    }
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+impl From<&Puzzle> for Profile {
+    fn from(puzzle: &Puzzle) -> Self {
+        Self::new(puzzle.name.clone(), puzzle.width, puzzle.height)
+    }
+}
+
+impl TryFrom<&Profile> for Puzzle {
+    type Error = PuzzleError;
+
+    fn try_from(profile: &Profile) -> Result<Self, Self::Error> {
+        profile.check()?;
+        Ok(Puzzle {
+            num_pieces: profile.num_pieces(),
+            name: profile.name.clone(),
+            width: profile.width,
+            height: profile.height,
+            pieces: profile.cut(),
+            history: Vec::new(),
+        })
+    }
+}
+
+impl TryFrom<Profile> for Puzzle {
+    type Error = PuzzleError;
+
+    fn try_from(profile: Profile) -> Result<Self, Self::Error> {
+        Puzzle::try_from(&profile)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::PUZZLE_PIECES;
+
+    #[test]
+    fn standard_profile_is_the_old_default() {
+        let puzzle = Puzzle::try_from(Profile::standard()).unwrap();
+        assert_eq!(puzzle.name, "Default");
+        assert_eq!(puzzle.num_pieces, PUZZLE_PIECES);
+        assert_eq!(Profile::from(&puzzle), Profile::standard());
+        assert!(puzzle.solve().is_ok());
+    }
+
+    #[test]
+    fn lays_pieces_out_nearly_square() {
+        let layout = |n| {
+            let profile = Profile::for_pieces("Lake", n).unwrap();
+            (profile.width, profile.height)
+        };
+        assert_eq!(layout(30), (6, 5));
+        assert_eq!(layout(42), (7, 6));
+        assert_eq!(layout(36), (6, 6));
+        assert_eq!(layout(13), (13, 1));
+        assert!(matches!(
+            Profile::for_pieces("Lake", 0),
+            Err(PuzzleError::BadDimensions { num_pieces: 0, .. })
+        ));
+        // 65537 은 소수라 한 줄로 늘어놓아야 하는데, 그러면 폭이 u16 을 넘습니다.
+        assert!(matches!(
+            Profile::for_pieces("Lake", 65537),
+            Err(PuzzleError::NoLayout(65537))
+        ));
+    }
+
+    #[test]
+    fn cuts_long_strips() {
+        for profile in [
+            Profile::new("Wide", 30000, 2),
+            Profile::for_pieces("Long", 65521).unwrap(),
+        ] {
+            let puzzle = Puzzle::try_from(&profile).unwrap();
+            assert_eq!(puzzle.pieces.len() as u32, profile.num_pieces());
+        }
+    }
+
+    #[test]
+    fn refuses_empty_layouts() {
+        assert!(matches!(
+            Puzzle::try_from(Profile::new("Nothing", 0, 4)),
+            Err(PuzzleError::BadDimensions { width: 0, .. })
+        ));
+    }
+}
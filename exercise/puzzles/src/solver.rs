@@ -0,0 +1,246 @@
+use crate::piece::{Edge, Piece, Rotation};
+use crate::{Board, Puzzle, PuzzleError};
+use log::{debug, info};
+
+/// How much work the solver had to do
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SolveStats {
+    /// Placements tried
+    pub nodes: u64,
+    /// Placements taken back because nothing fit after them
+    pub backtracks: u64,
+}
+
+/// A finished board and what it cost to get there
+#[derive(Debug, Clone)]
+pub struct Solution {
+    pub board: Board,
+    pub stats: SolveStats,
+}
+
+/// Solve a shuffled puzzle by backtracking
+///
+/// Corners are placed first, then the rest of the border, then the interior
+/// row by row. Each cell only considers pieces with the same number of flat
+/// edges as the cell has sides on the border, in every rotation.
+pub fn solve(puzzle: &Puzzle) -> Result<Solution, PuzzleError> {
+    let mut solver = Solver::new(puzzle)?;
+    let solved = solver.search(0);
+    let stats = solver.stats;
+    info!(
+        "Solver explored {} nodes with {} backtracks",
+        stats.nodes, stats.backtracks
+    );
+    if solved {
+        Ok(Solution {
+            board: solver.board,
+            stats,
+        })
+    } else {
+        // 가장 깊이 들어갔을 때 남아 있던 조각이 끝내 놓지 못한 조각입니다.
+        debug!("Deepest placement reached: {}", solver.deepest);
+        Err(PuzzleError::WontFit(solver.stuck.unwrap_or(0)))
+    }
+}
+
+struct Solver<'a> {
+    pieces: &'a [Piece],
+    /// Cells in the order they get filled
+    cells: Vec<(u16, u16)>,
+    /// Number of board edges each cell touches, by position in `cells`
+    flats: Vec<usize>,
+    used: Vec<bool>,
+    board: Board,
+    stats: SolveStats,
+    deepest: usize,
+    stuck: Option<u16>,
+}
+
+impl<'a> Solver<'a> {
+    fn new(puzzle: &'a Puzzle) -> Result<Self, PuzzleError> {
+        let (width, height) = (puzzle.width, puzzle.height);
+        let cells = fill_order(width, height);
+        if puzzle.pieces.len() < cells.len() {
+            return Err(PuzzleError::MissingPiece);
+        }
+        if let Some(extra) = puzzle.pieces.get(cells.len()) {
+            return Err(PuzzleError::WontFit(extra.id));
+        }
+        let flats: Vec<usize> = cells
+            .iter()
+            .map(|&(x, y)| border_sides(x, y, width, height))
+            .collect();
+
+        // 모서리 조각 수가 모서리 칸 수와 다르면 찾아볼 필요도 없습니다.
+        // 같은 종류의 칸보다 많은 조각 중 첫 번째를 맞지 않는 조각으로 알려 줍니다.
+        for class in 0..=4 {
+            let slots = flats.iter().filter(|&&f| f == class).count();
+            let mut candidates = puzzle.pieces.iter().filter(|p| p.flat_edges() == class);
+            if let Some(extra) = candidates.nth(slots) {
+                return Err(PuzzleError::WontFit(extra.id));
+            }
+        }
+
+        // 짝이 될 변이 아무 조각에도 없는 변이 있다면, 그 조각은 어디에도 놓을 수 없습니다.
+        for (i, piece) in puzzle.pieces.iter().enumerate() {
+            let unmatched = piece.edges.iter().any(|&edge| {
+                edge != Edge::Flat
+                    && !puzzle
+                        .pieces
+                        .iter()
+                        .enumerate()
+                        .any(|(j, other)| i != j && other.edges.iter().any(|&o| edge.fits(o)))
+            });
+            if unmatched {
+                return Err(PuzzleError::WontFit(piece.id));
+            }
+        }
+
+        Ok(Solver {
+            pieces: &puzzle.pieces,
+            cells,
+            flats,
+            used: vec![false; puzzle.pieces.len()],
+            board: puzzle.board(),
+            stats: SolveStats::default(),
+            deepest: 0,
+            stuck: None,
+        })
+    }
+
+    fn search(&mut self, depth: usize) -> bool {
+        if depth == self.cells.len() {
+            return true;
+        }
+        let (x, y) = self.cells[depth];
+        for i in 0..self.pieces.len() {
+            if self.used[i] || self.pieces[i].flat_edges() != self.flats[depth] {
+                continue;
+            }
+            let mut piece = self.pieces[i];
+            for rotation in Rotation::ALL {
+                piece.rotation = rotation;
+                if !self.board.fits(&piece, x, y) {
+                    continue;
+                }
+                self.stats.nodes += 1;
+                self.board
+                    .place(piece, x, y)
+                    .expect("fits() said the piece fits");
+                self.used[i] = true;
+                if self.search(depth + 1) {
+                    return true;
+                }
+                self.used[i] = false;
+                self.board.undo();
+                self.stats.backtracks += 1;
+            }
+        }
+        if depth >= self.deepest {
+            self.deepest = depth;
+            self.stuck = self
+                .pieces
+                .iter()
+                .zip(&self.used)
+                .find(|&(piece, &used)| !used && piece.flat_edges() == self.flats[depth])
+                .map(|(piece, _)| piece.id);
+        }
+        false
+    }
+}
+
+/// How many sides of the cell at `(x, y)` are on the border of the board
+fn border_sides(x: u16, y: u16, width: u16, height: u16) -> usize {
+    [y == 0, x + 1 == width, y + 1 == height, x == 0]
+        .iter()
+        .filter(|&&edge| edge)
+        .count()
+}
+
+/// Corners, then the border clockwise from the top left, then the interior row by row
+fn fill_order(width: u16, height: u16) -> Vec<(u16, u16)> {
+    let mut ring = Vec::new();
+    if width > 0 && height > 0 {
+        ring.extend((0..width).map(|x| (x, 0)));
+        ring.extend((1..height).map(|y| (width - 1, y)));
+        if height > 1 {
+            ring.extend((0..width - 1).rev().map(|x| (x, height - 1)));
+        }
+        if width > 1 {
+            ring.extend((1..height - 1).rev().map(|y| (0, y)));
+        }
+    }
+    let interior = (1..height.saturating_sub(1))
+        .flat_map(|y| (1..width.saturating_sub(1)).map(move |x| (x, y)));
+
+    let mut cells: Vec<(u16, u16)> = ring
+        .iter()
+        .copied()
+        .filter(|&(x, y)| border_sides(x, y, width, height) >= 2)
+        .collect();
+    cells.extend(
+        ring.iter()
+            .copied()
+            .filter(|&(x, y)| border_sides(x, y, width, height) == 1),
+    );
+    cells.extend(interior);
+    cells
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn shuffled(mut puzzle: Puzzle) -> Puzzle {
+        puzzle.pieces.reverse();
+        for (i, piece) in puzzle.pieces.iter_mut().enumerate() {
+            piece.rotation = Rotation::from_quarters(i as u8);
+        }
+        puzzle
+    }
+
+    #[test]
+    fn fill_order_visits_every_cell_once() {
+        for (width, height) in [(1, 1), (1, 4), (4, 1), (2, 2), (5, 3)] {
+            let mut cells = fill_order(width, height);
+            assert_eq!(cells.len(), width as usize * height as usize);
+            assert!(border_sides(cells[0].0, cells[0].1, width, height) >= 2);
+            cells.sort();
+            cells.dedup();
+            assert_eq!(cells.len(), width as usize * height as usize);
+        }
+    }
+
+    #[test]
+    fn solves_the_default_puzzle() {
+        let puzzle = shuffled(Puzzle::new());
+        let solution = solve(&puzzle).unwrap();
+        assert!(solution.board.is_complete());
+        assert!(solution.stats.nodes >= puzzle.num_pieces as u64);
+    }
+
+    #[test]
+    fn reports_a_piece_that_cant_be_placed() {
+        let mut puzzle = shuffled(Puzzle::new());
+        // 안쪽 조각 하나의 모양을 바꿔 어디에도 맞지 않게 만듭니다.
+        // 원래 짝이었던 위쪽 이웃도 짝을 잃기 때문에 둘 중 하나가 보고됩니다.
+        let broken = puzzle
+            .pieces
+            .iter_mut()
+            .find(|piece| piece.flat_edges() == 0)
+            .unwrap();
+        broken.edges[0] = Edge::Tab(200);
+        let suspects = [broken.id, broken.id - puzzle.width];
+        match solve(&puzzle) {
+            Err(PuzzleError::WontFit(id)) => assert!(suspects.contains(&id)),
+            other => panic!("expected WontFit, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn reports_missing_pieces() {
+        let mut puzzle = Puzzle::new();
+        puzzle.pieces.pop();
+        assert!(matches!(solve(&puzzle), Err(PuzzleError::MissingPiece)));
+    }
+}
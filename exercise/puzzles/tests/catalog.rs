@@ -0,0 +1,116 @@
+use puzzles::{Catalog, Difficulty, Progress, Puzzle, PuzzleError};
+use std::path::Path;
+
+fn save(dir: &Path, file: &str, name: &str, width: u16, height: u16) -> Puzzle {
+    let mut puzzle = Puzzle::generate(width, height, 3).unwrap();
+    puzzle.name = name.to_string();
+    puzzle.save(dir.join(file)).unwrap();
+    puzzle
+}
+
+// 여러 크기와 진행 상태의 퍼즐이 든 디렉터리를 만듭니다.
+fn shelf() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path();
+    save(path, "forest.dat", "Forest Lake", 25, 20);
+    save(path, "big.dat", "Mountain Lake", 40, 30);
+    save(path, "city.dat", "City Lights", 10, 10);
+
+    let mut solved = save(path, "small.dat", "Small Lake", 3, 2);
+    solved.history = solved.solve().unwrap().board.history().to_vec();
+    solved.save(path.join("small.dat")).unwrap();
+
+    let mut started = save(path, "desert.dat", "Desert", 4, 3);
+    let solution = started.solve().unwrap();
+    started.history = solution.board.history()[..5].to_vec();
+    started.save(path.join("desert.dat")).unwrap();
+
+    std::fs::write(path.join("broken.dat"), b"PUZL\x09").unwrap();
+    std::fs::write(path.join("notes.txt"), b"not a puzzle").unwrap();
+    dir
+}
+
+#[test]
+fn indexes_every_puzzle_in_the_directory() {
+    let dir = shelf();
+    let catalog = Catalog::open(dir.path()).unwrap();
+    let names: Vec<&str> = catalog.entries().iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(
+        names,
+        vec![
+            "City Lights",
+            "Desert",
+            "Forest Lake",
+            "Mountain Lake",
+            "Small Lake"
+        ]
+    );
+
+    // 읽을 수 없는 .dat 파일은 건너뛰고, 다른 확장자는 아예 보지 않습니다.
+    assert_eq!(catalog.skipped().len(), 1);
+    assert!(catalog.skipped()[0].0.ends_with("broken.dat"));
+
+    let forest = catalog.get("forest lake").unwrap();
+    assert_eq!(forest.num_pieces, 500);
+    // 500 조각에 탭 모양이 16 가지뿐이라, 변 하나에 맞는 짝 후보가 여럿입니다.
+    assert!(forest.score.ambiguity > 10.0);
+    assert_eq!(forest.difficulty, Difficulty::Expert);
+    assert_eq!(forest.difficulty, forest.score.difficulty());
+    assert_eq!(
+        catalog.get("Small Lake").unwrap().difficulty,
+        Difficulty::Easy
+    );
+    assert_eq!(forest.progress, Progress::New);
+    assert_eq!(
+        catalog.get("Small Lake").unwrap().progress,
+        Progress::Solved
+    );
+    assert_eq!(
+        catalog.get("Desert").unwrap().progress,
+        Progress::Started { placed: 5 }
+    );
+    assert_eq!(forest.load().unwrap().num_pieces, 500);
+}
+
+#[test]
+fn queries_combine_their_conditions() {
+    let dir = shelf();
+    let catalog = Catalog::open(dir.path()).unwrap();
+    let names = |query: puzzles::Query| -> Vec<String> {
+        query.run().iter().map(|e| e.name.clone()).collect()
+    };
+
+    assert_eq!(
+        names(catalog.query().min_pieces(500)),
+        vec!["Forest Lake", "Mountain Lake"]
+    );
+    assert_eq!(
+        names(catalog.query().name_contains("LAKE").max_pieces(500)),
+        vec!["Forest Lake", "Small Lake"]
+    );
+    assert_eq!(
+        names(catalog.query().difficulty(Difficulty::Expert)),
+        vec!["Forest Lake", "Mountain Lake"]
+    );
+    assert_eq!(names(catalog.query().solved(true)), vec!["Small Lake"]);
+    assert_eq!(catalog.query().solved(false).run().len(), 4);
+    assert_eq!(catalog.query().run().len(), 5);
+}
+
+#[test]
+fn ignores_case_beyond_ascii() {
+    let dir = tempfile::tempdir().unwrap();
+    save(dir.path(), "olberg.dat", "Ölberg", 3, 2);
+    let catalog = Catalog::open(dir.path()).unwrap();
+    // 검색에 걸리는 이름은 get 으로도 찾을 수 있어야 합니다.
+    assert_eq!(catalog.query().name_contains("ölb").run().len(), 1);
+    assert!(catalog.get("ÖLBERG").is_some());
+    assert!(catalog.get("ölberg").is_some());
+}
+
+#[test]
+fn reports_a_missing_directory() {
+    let dir = tempfile::tempdir().unwrap();
+    let err = Catalog::open(dir.path().join("nowhere")).unwrap_err();
+    assert!(matches!(err, PuzzleError::Catalog { .. }));
+}
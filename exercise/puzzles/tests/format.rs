@@ -0,0 +1,397 @@
+use puzzles::{DamageReport, Edge, Move, Puzzle, PuzzleError, Rotation, Side};
+use std::fs::File;
+use std::io::{Seek, SeekFrom, Write};
+
+// 테스트는 crate 루트에서 실행되므로 fixture 경로는 crate 기준입니다.
+fn load(name: &str) -> Result<Puzzle, PuzzleError> {
+    let fh = File::open(format!("tests/fixtures/{}", name)).expect("fixture is missing");
+    Puzzle::from_file(fh)
+}
+
+#[test]
+fn loads_a_valid_puzzle() {
+    let puzzle = load("forest_lake.dat").unwrap();
+    assert_eq!(puzzle.name, "Forest Lake");
+    assert_eq!(puzzle.num_pieces, 4);
+    assert_eq!((puzzle.width, puzzle.height), (2, 2));
+    let ids: Vec<u16> = puzzle.pieces.iter().map(|piece| piece.id).collect();
+    assert_eq!(ids, vec![2, 0, 3, 1]);
+
+    let corner = puzzle.piece(0).unwrap();
+    assert_eq!(
+        corner.edges,
+        [Edge::Flat, Edge::Tab(1), Edge::Blank(2), Edge::Flat]
+    );
+    let turned = puzzle.piece(3).unwrap();
+    assert_eq!(turned.rotation, Rotation::R90);
+    assert_eq!(turned.edge(Side::Top), Edge::Tab(4));
+}
+
+#[test]
+fn fixture_pieces_solve_the_board() {
+    let puzzle = load("forest_lake.dat").unwrap();
+    let mut board = puzzle.board();
+    for piece in &puzzle.pieces {
+        let mut piece = *piece;
+        piece.rotation = Rotation::R0;
+        board
+            .place(piece, piece.id % puzzle.width, piece.id / puzzle.width)
+            .unwrap();
+    }
+    assert!(board.is_complete());
+}
+
+#[test]
+fn pins_the_header_layout() {
+    let data = std::fs::read("tests/fixtures/forest_lake.dat").unwrap();
+    assert_eq!(&data[0..4], b"PUZL");
+    assert_eq!(data[4], 2);
+    assert_eq!(&data[5..7], &[11, 0]);
+    assert_eq!(&data[7..18], b"Forest Lake");
+    assert_eq!(&data[18..22], &[2, 0, 2, 0]);
+    assert_eq!(&data[22..26], &[4, 0, 0, 0]);
+    // 첫 번째 조각: 번호 2, 회전 없음, 위 볼록(2), 오른쪽 오목(4), 아래와 왼쪽은 평평
+    assert_eq!(&data[26..37], &[2, 0, 0, 1, 2, 2, 4, 0, 0, 0, 0]);
+    assert_eq!(data.len(), 26 + 4 * 11);
+}
+
+#[test]
+fn rejects_bad_magic() {
+    let err = load("bad_magic.dat").unwrap_err();
+    assert!(matches!(err.kind(), PuzzleError::BadMagic));
+    assert_eq!(err.offset(), Some(0));
+}
+
+#[test]
+fn rejects_unknown_versions() {
+    let err = load("future_version.dat").unwrap_err();
+    assert!(matches!(err.kind(), PuzzleError::UnsupportedVersion(9)));
+    assert_eq!(err.offset(), Some(4));
+}
+
+#[test]
+fn rejects_a_truncated_piece_table() {
+    let err = load("truncated_pieces.dat").unwrap_err();
+    assert!(matches!(
+        err.kind(),
+        PuzzleError::TruncatedPieceTable {
+            expected: 4,
+            found: 2
+        }
+    ));
+    // 세 번째 조각 기록이 시작하는 곳
+    assert_eq!(err.offset(), Some(26 + 2 * 11));
+}
+
+#[test]
+fn rejects_pieces_out_of_range() {
+    let err = load("out_of_range.dat").unwrap_err();
+    assert!(matches!(err.kind(), PuzzleError::WontFit(7)));
+    assert!(err.offset().unwrap() >= 26);
+}
+
+#[test]
+fn rejects_the_same_piece_twice() {
+    let mut puzzle = Puzzle::new();
+    puzzle.pieces[1].id = puzzle.pieces[0].id;
+    assert!(matches!(
+        puzzle.write_to(Vec::new()),
+        Err(PuzzleError::WontFit(0))
+    ));
+
+    // 손으로 고친 파일도 읽지 않습니다: 두 번째 조각 (번호 0) 을 첫 번째와 같은 번호 2 로 바꿉니다.
+    let mut data = std::fs::read("tests/fixtures/forest_lake.dat").unwrap();
+    data[26 + 11] = 2;
+    let err = Puzzle::from_reader(&data[..]).unwrap_err();
+    assert!(matches!(err.kind(), PuzzleError::WontFit(2)));
+    assert_eq!(err.offset(), Some(26 + 11));
+}
+
+#[test]
+fn rejects_a_truncated_header() {
+    let err = Puzzle::from_reader(&b"PUZL\x02\x0b\x00Forest"[..]).unwrap_err();
+    assert!(matches!(err.kind(), PuzzleError::TruncatedHeader));
+    assert_eq!(err.offset(), Some(7));
+}
+
+#[test]
+fn rejects_dimensions_that_dont_match_the_piece_count() {
+    let mut data = std::fs::read("tests/fixtures/forest_lake.dat").unwrap();
+    data[18] = 3;
+    let err = Puzzle::from_reader(&data[..]).unwrap_err();
+    assert!(matches!(
+        err.kind(),
+        PuzzleError::BadDimensions {
+            width: 3,
+            height: 2,
+            num_pieces: 4
+        }
+    ));
+    assert_eq!(err.offset(), Some(18));
+}
+
+#[test]
+fn rejects_malformed_edges() {
+    let mut data = std::fs::read("tests/fixtures/forest_lake.dat").unwrap();
+    // 첫 번째 조각의 위쪽 변 종류
+    data[29] = 7;
+    let err = Puzzle::from_reader(&data[..]).unwrap_err();
+    assert!(matches!(err.kind(), PuzzleError::BadPiece(2)));
+    assert_eq!(err.offset(), Some(26));
+}
+
+#[test]
+fn loads_the_move_history() {
+    // 버전 2 파일에는 기록이 없습니다.
+    assert!(load("forest_lake.dat").unwrap().history.is_empty());
+
+    let puzzle = load("forest_lake_v3.dat").unwrap();
+    assert_eq!(puzzle.history.len(), 3);
+    match puzzle.history[2] {
+        Move::Remove { piece, x, y } => assert_eq!((piece.id, x, y), (1, 1, 0)),
+        other => panic!("expected a removal, got {:?}", other),
+    }
+
+    // 기록을 한 수씩 재생하면서 보드 상태를 따라가 볼 수 있습니다.
+    let mut board = puzzle.board();
+    let placed: Vec<usize> = puzzle
+        .history
+        .iter()
+        .map(|mv| {
+            board.apply(*mv).unwrap();
+            board.placed()
+        })
+        .collect();
+    assert_eq!(placed, vec![1, 2, 1]);
+
+    let mut resumed = puzzle.resume().unwrap();
+    assert_eq!(resumed, board);
+    resumed.undo();
+    assert_eq!(resumed.position_of(1), Some((1, 0)));
+}
+
+#[test]
+fn rejects_a_truncated_history() {
+    let data = std::fs::read("tests/fixtures/forest_lake_v3.dat").unwrap();
+    let pieces_end = 26 + 4 * 11;
+    let err = Puzzle::from_reader(&data[..data.len() - 1]).unwrap_err();
+    assert!(matches!(
+        err.kind(),
+        PuzzleError::TruncatedHistory {
+            expected: 3,
+            found: 2
+        }
+    ));
+    assert_eq!(err.offset(), Some(pieces_end + 4 + 2 * 16));
+    let err = Puzzle::from_reader(&data[..pieces_end]).unwrap_err();
+    assert!(matches!(err.kind(), PuzzleError::MissingHistory));
+    assert_eq!(err.offset(), Some(pieces_end));
+}
+
+#[test]
+fn writes_fixture_bytes_back_exactly() {
+    let data = std::fs::read("tests/fixtures/forest_lake_v4.dat").unwrap();
+    let puzzle = Puzzle::from_reader(&data[..]).unwrap();
+    let mut written = Vec::new();
+    puzzle.write_to(&mut written).unwrap();
+    assert_eq!(written, data);
+}
+
+#[test]
+fn default_puzzle_round_trips() {
+    let puzzle = Puzzle::new();
+    let mut written = Vec::new();
+    puzzle.write_to(&mut written).unwrap();
+    let loaded = Puzzle::from_reader(&written[..]).unwrap();
+    assert_eq!(loaded.name, puzzle.name);
+    assert_eq!(loaded.num_pieces, puzzle.num_pieces);
+    assert_eq!((loaded.width, loaded.height), (puzzle.width, puzzle.height));
+    assert_eq!(loaded.pieces, puzzle.pieces);
+    assert!(loaded.history.is_empty());
+}
+
+#[test]
+fn refuses_to_write_an_inconsistent_puzzle() {
+    let mut puzzle = Puzzle::new();
+    puzzle.pieces.pop();
+    assert!(matches!(
+        puzzle.write_to(Vec::new()),
+        Err(PuzzleError::MissingPiece)
+    ));
+
+    let mut puzzle = Puzzle::new();
+    puzzle.pieces[3].id = 500;
+    assert!(matches!(
+        puzzle.write_to(Vec::new()),
+        Err(PuzzleError::WontFit(500))
+    ));
+}
+
+#[test]
+fn save_replaces_the_file_atomically() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("puzzle.dat");
+    std::fs::write(&path, b"old contents").unwrap();
+
+    let puzzle = load("forest_lake.dat").unwrap();
+    puzzle.save(&path).unwrap();
+
+    let saved = Puzzle::from_file(File::open(&path).unwrap()).unwrap();
+    assert_eq!(saved.pieces, puzzle.pieces);
+    // 임시 파일은 rename 되어 남지 않아야 합니다.
+    assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+}
+
+#[test]
+fn failed_save_keeps_the_old_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("puzzle.dat");
+    std::fs::write(&path, b"old contents").unwrap();
+
+    let mut puzzle = Puzzle::new();
+    puzzle.pieces.clear();
+    assert!(puzzle.save(&path).is_err());
+    assert_eq!(std::fs::read(&path).unwrap(), b"old contents");
+    assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+}
+
+#[test]
+fn errors_chain_to_their_cause() {
+    use std::error::Error;
+
+    let mut data = std::fs::read("tests/fixtures/forest_lake.dat").unwrap();
+    // 이름 안에 UTF-8 이 아닌 byte
+    data[9] = 0xff;
+    let err = Puzzle::from_reader(&data[..]).unwrap_err();
+    assert_eq!(err.to_string(), "Invalid puzzle data at byte 7");
+    let cause = err.source().unwrap();
+    assert_eq!(cause.to_string(), "The puzzle name is not valid UTF-8");
+    assert!(cause.source().is_some());
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("no such dir").join("puzzle.dat");
+    let err = Puzzle::new().save(&path).unwrap_err();
+    assert!(matches!(err, PuzzleError::Save { .. }));
+    assert!(err.to_string().contains("no such dir"));
+    assert!(err.source().is_some());
+}
+
+#[test]
+fn rejects_a_checksum_mismatch() {
+    let v3 = load("forest_lake_v3.dat").unwrap();
+    let v4 = load("forest_lake_v4.dat").unwrap();
+    assert_eq!(v4.pieces, v3.pieces);
+    assert_eq!(v4.history, v3.history);
+
+    let mut data = std::fs::read("tests/fixtures/forest_lake_v4.dat").unwrap();
+    // 손으로 고친 것처럼 조각 하나의 회전만 바꿉니다.
+    data[28] = 1;
+    let err = Puzzle::from_reader(&data[..]).unwrap_err();
+    assert!(matches!(err.kind(), PuzzleError::ChecksumMismatch { .. }));
+    assert_eq!(err.offset(), Some(data.len() - 4));
+}
+
+fn repair(data: &[u8]) -> (Puzzle, DamageReport) {
+    let mut fh = tempfile::tempfile().unwrap();
+    fh.write_all(data).unwrap();
+    fh.seek(SeekFrom::Start(0)).unwrap();
+    Puzzle::repair_from_file(fh).unwrap()
+}
+
+#[test]
+fn repairing_an_intact_file_changes_nothing() {
+    let data = std::fs::read("tests/fixtures/forest_lake_v4.dat").unwrap();
+    let (puzzle, report) = repair(&data);
+    assert!(report.is_clean());
+    assert_eq!(puzzle.pieces.len(), 4);
+    assert_eq!(puzzle.history.len(), 3);
+}
+
+#[test]
+fn repair_drops_broken_pieces_and_the_moves_that_used_them() {
+    let mut data = std::fs::read("tests/fixtures/forest_lake_v4.dat").unwrap();
+    // 두 번째 조각 (번호 0) 의 오른쪽 변 종류를 망가뜨립니다.
+    data[26 + 11 + 5] = 9;
+    let (puzzle, report) = repair(&data);
+
+    let ids: Vec<u16> = puzzle.pieces.iter().map(|piece| piece.id).collect();
+    assert_eq!(ids, vec![2, 3, 1]);
+    assert_eq!(report.missing, vec![0]);
+    // 체크섬, 망가진 조각, 그리고 없는 조각을 놓는 첫 번째 수
+    let kinds: Vec<String> = report
+        .problems
+        .iter()
+        .map(|e| e.kind().to_string())
+        .collect();
+    assert_eq!(report.problems.len(), 3, "{:?}", kinds);
+    assert!(matches!(
+        report.problems[0].kind(),
+        PuzzleError::ChecksumMismatch { .. }
+    ));
+    assert!(matches!(
+        report.problems[1].kind(),
+        PuzzleError::BadPiece(0)
+    ));
+    assert_eq!(report.problems[1].offset(), Some(26 + 11));
+    assert!(matches!(
+        report.problems[2].kind(),
+        PuzzleError::MissingPiece
+    ));
+    assert_eq!(report.dropped_moves, 3);
+    assert!(puzzle.history.is_empty());
+
+    // 빠진 조각이 있는 퍼즐은 풀 수도 저장할 수도 없습니다.
+    assert!(matches!(puzzle.solve(), Err(PuzzleError::MissingPiece)));
+    assert!(report.to_string().contains("Missing pieces: 0"));
+    // 위치와 문제를 한 번씩만 적습니다.
+    assert!(report
+        .to_string()
+        .contains("Invalid puzzle data at byte 37: Piece 0 has a malformed record\n"));
+}
+
+#[test]
+fn repair_keeps_the_readable_part_of_a_cut_off_file() {
+    let data = std::fs::read("tests/fixtures/forest_lake_v4.dat").unwrap();
+    let (puzzle, report) = repair(&data[..26 + 4 * 11 + 4 + 16 + 7]);
+    assert_eq!(puzzle.pieces.len(), 4);
+    assert!(report.missing.is_empty());
+    assert_eq!(puzzle.history.len(), 1);
+    assert_eq!(report.dropped_moves, 2);
+
+    // 헤더조차 없으면 건질 것이 없습니다.
+    let mut fh = tempfile::tempfile().unwrap();
+    fh.write_all(b"PUZL\x04").unwrap();
+    fh.seek(SeekFrom::Start(0)).unwrap();
+    assert!(Puzzle::repair_from_file(fh).is_err());
+}
+
+/// A version 3 file with no name and no piece table after the header
+fn header_only(width: u16, height: u16) -> Vec<u8> {
+    let mut data = b"PUZL\x03\x00\x00".to_vec();
+    data.extend_from_slice(&width.to_le_bytes());
+    data.extend_from_slice(&height.to_le_bytes());
+    data.extend_from_slice(&(u32::from(width) * u32::from(height)).to_le_bytes());
+    data
+}
+
+#[test]
+fn repair_doesnt_trust_the_piece_count_in_the_header() {
+    // 몇 byte 짜리 파일이 40 억 개가 넘는 조각을 주장해도 메모리를 잡지 않고 거부합니다.
+    let mut fh = tempfile::tempfile().unwrap();
+    fh.write_all(&header_only(u16::MAX, u16::MAX)).unwrap();
+    fh.seek(SeekFrom::Start(0)).unwrap();
+    let err = Puzzle::repair_from_file(fh).unwrap_err();
+    assert!(matches!(err.kind(), PuzzleError::BadDimensions { .. }));
+
+    // 조각 번호로 셀 수 있는 만큼이라도, 잘린 테이블은 번호를 하나하나 늘어놓지 않습니다.
+    let (puzzle, report) = repair(&header_only(256, 256));
+    assert!(puzzle.pieces.is_empty());
+    assert!(report.missing.is_empty());
+    assert!(matches!(
+        report.problems[0].kind(),
+        PuzzleError::TruncatedPieceTable {
+            expected: 65536,
+            found: 0
+        }
+    ));
+}
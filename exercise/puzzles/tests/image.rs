@@ -0,0 +1,60 @@
+use puzzles::{ImagePuzzle, Manifest, Puzzle, PuzzleError};
+use std::fs::File;
+
+fn write_picture(path: &std::path::Path) {
+    let picture =
+        image::RgbaImage::from_fn(90, 60, |x, y| image::Rgba([x as u8, y as u8, 100, 255]));
+    picture.save(path).unwrap();
+}
+
+#[test]
+fn exports_pieces_and_a_manifest() {
+    let dir = tempfile::tempdir().unwrap();
+    let source = dir.path().join("forest_lake.png");
+    write_picture(&source);
+
+    let cut = ImagePuzzle::open(&source, 3, 2, 11).unwrap();
+    assert_eq!(cut.puzzle.name, "forest_lake");
+    let out = dir.path().join("pieces");
+    let manifest = cut.export(&out).unwrap();
+
+    assert_eq!(Manifest::load(&out).unwrap(), manifest);
+    assert_eq!((manifest.width, manifest.height), (3, 2));
+    assert_eq!(manifest.pieces.len(), 6);
+    for entry in &manifest.pieces {
+        let piece = image::open(out.join(&entry.file)).unwrap();
+        assert_eq!(
+            (piece.width(), piece.height()),
+            (entry.image_width, entry.image_height)
+        );
+    }
+    // 같이 저장된 퍼즐 파일은 그대로 불러와서 풀 수 있습니다.
+    let puzzle = Puzzle::from_file(File::open(out.join(&manifest.puzzle)).unwrap()).unwrap();
+    assert_eq!(puzzle.pieces, cut.puzzle.pieces);
+    assert!(puzzle.solve().is_ok());
+}
+
+#[test]
+fn shrinks_big_pictures_to_fit() {
+    let dir = tempfile::tempdir().unwrap();
+    let source = dir.path().join("forest_lake.png");
+    write_picture(&source);
+
+    // 90x60 을 45x45 안에 맞추면 모양을 지킨 채 45x30 이 됩니다.
+    let cut = ImagePuzzle::open_fitted(&source, 3, 2, 45, 45, 11).unwrap();
+    assert_eq!(cut.puzzle.name, "forest_lake");
+    assert_eq!((cut.cell_width, cut.cell_height), (15, 15));
+    // 이미 들어가는 그림은 키우지 않습니다.
+    let cut = ImagePuzzle::open_fitted(&source, 3, 2, 480, 360, 11).unwrap();
+    assert_eq!((cut.cell_width, cut.cell_height), (30, 30));
+}
+
+#[test]
+fn reports_an_unreadable_image() {
+    let dir = tempfile::tempdir().unwrap();
+    let source = dir.path().join("not_a_picture.png");
+    std::fs::write(&source, b"hello").unwrap();
+    let err = ImagePuzzle::open(&source, 3, 2, 0).unwrap_err();
+    assert!(matches!(err, PuzzleError::Image { .. }));
+    assert!(std::error::Error::source(&err).is_some());
+}
@@ -0,0 +1,25 @@
+use puzzles::{Profile, Puzzle, PuzzleError};
+
+// 기본 프로필은 프로그램 전체에서 한 번만 정할 수 있으니, 다른 테스트와 섞이지 않도록 파일을 따로 둡니다.
+#[test]
+fn default_profile_is_set_once() {
+    // 잘못된 프로필은 기본값이 되지 않고, 나중에 제대로 된 것을 정할 수 있습니다.
+    assert!(matches!(
+        Profile::set_default(Profile::new("Empty", 0, 0)),
+        Err(PuzzleError::BadDimensions { .. })
+    ));
+
+    Profile::set_default(Profile::new("Forest Lake", 6, 5)).unwrap();
+    let puzzle = Puzzle::new();
+    assert_eq!(puzzle.name, "Forest Lake");
+    assert_eq!(puzzle.num_pieces, 30);
+    assert_eq!((puzzle.width, puzzle.height), (6, 5));
+    assert!(puzzle.solve().is_ok());
+
+    // 한 번 정한 기본값은 바뀌지 않습니다.
+    assert!(matches!(
+        Profile::set_default(Profile::standard()),
+        Err(PuzzleError::DefaultProfileSet(name)) if name == "Default"
+    ));
+    assert_eq!(Profile::current().name, "Forest Lake");
+}
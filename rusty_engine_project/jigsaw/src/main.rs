@@ -0,0 +1,271 @@
+use puzzles::{Board, Fractal, ImagePuzzle, Manifest, Puzzle, PuzzleError, Rotation};
+use rand::prelude::*;
+use rusty_engine::prelude::*;
+use std::f32::consts::FRAC_PI_2;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+/// Folder under `assets/` the cut puzzle lives in
+const PUZZLE_DIR: &str = "puzzle";
+/// Largest picture that still leaves room on screen for the board and the loose pieces
+const PICTURE_WIDTH: u32 = 480;
+const PICTURE_HEIGHT: u32 = 360;
+/// How close to a cell a piece has to be dropped to snap into it, as a share of the cell
+const SNAP: f32 = 0.35;
+/// Pieces on the board stay under the loose ones
+const BOARD_LAYER: f32 = 0.5;
+
+struct GameState {
+    puzzle: Puzzle,
+    board: Board,
+    manifest: Manifest,
+    /// Top left corner of the solved picture on screen
+    origin: Vec2,
+    /// Piece being dragged, and how far its middle is from the mouse
+    held: Option<(u16, Vec2)>,
+    /// Layer for the next piece that gets picked up, so it is drawn on top
+    top_layer: f32,
+    solved: bool,
+}
+
+fn main() -> Result<(), PuzzleError> {
+    // cargo run -- <그림.png> 로 원하는 그림을 자를 수 있습니다.
+    // 아무것도 주지 않으면 예전에 잘라 둔 퍼즐을 쓰고, 그것도 없으면 fractal 을 그려서 자릅니다.
+    let image = std::env::args().nth(1).map(PathBuf::from);
+    let (puzzle, manifest) = load_puzzle(image)?;
+
+    let mut game = Game::new();
+    game.window_settings(WindowDescriptor {
+        title: format!("Jigsaw: {}", puzzle.name),
+        ..Default::default()
+    });
+
+    let cell = Vec2::new(manifest.cell_width as f32, manifest.cell_height as f32);
+    let picture = cell * Vec2::new(f32::from(manifest.width), f32::from(manifest.height));
+    // 왼쪽에 맞춰질 자리를 두고, 오른쪽에 조각들을 흩어 놓습니다.
+    let origin = Vec2::new(-picture.x - 40.0, picture.y / 2.0);
+    let mut rng = thread_rng();
+    // 예전에 큰 조각으로 잘라 둔 퍼즐이라도 흩어 놓을 범위가 비지 않게 합니다.
+    let scatter = |low: f32, high: f32| low..high.max(low + 1.0);
+    for entry in &manifest.pieces {
+        let piece = puzzle
+            .piece(entry.id)
+            .copied()
+            .ok_or(PuzzleError::MissingPiece)?;
+        let sprite = game.add_sprite(label(entry.id), Path::new(PUZZLE_DIR).join(&entry.file));
+        sprite.translation = Vec2::new(
+            rng.gen_range(scatter(80.0, 560.0 - cell.x / 2.0)),
+            rng.gen_range(scatter(-300.0 + cell.y / 2.0, 280.0 - cell.y / 2.0)),
+        );
+        sprite.rotation = sprite_rotation(piece.rotation);
+        sprite.layer = 1.0 + rng.gen_range(0.0..0.5);
+    }
+
+    let frame = game.add_text("frame", "Put the picture together here");
+    frame.translation = origin + Vec2::new(picture.x / 2.0, 20.0);
+    frame.font_size = 20.0;
+    let status = game.add_text("status", "");
+    status.translation = Vec2::new(0.0, -330.0);
+    status.font_size = 24.0;
+
+    game.audio_manager
+        .play_music(MusicPreset::WhimsicalPopsicle, 0.1);
+    game.add_logic(game_logic);
+    game.run(GameState {
+        board: puzzle.board(),
+        puzzle,
+        manifest,
+        origin,
+        held: None,
+        top_layer: 2.0,
+        solved: false,
+    });
+    Ok(())
+}
+
+/// Cut `image` if given, otherwise reuse the last cut puzzle or cut a fractal
+fn load_puzzle(image: Option<PathBuf>) -> Result<(Puzzle, Manifest), PuzzleError> {
+    let dir = assets_dir().join(PUZZLE_DIR);
+    let seed = thread_rng().gen();
+    let cut = match image {
+        // 사진은 화면에 들어가도록 줄여서 자릅니다.
+        Some(image) => Some(ImagePuzzle::open_fitted(
+            image,
+            4,
+            3,
+            PICTURE_WIDTH,
+            PICTURE_HEIGHT,
+            seed,
+        )?),
+        None if !dir.join(puzzles::MANIFEST_FILE).exists() => {
+            let julia = Fractal::Julia {
+                c: (-0.8, 0.156),
+                max_iterations: 256,
+            };
+            Some(ImagePuzzle::from_fractal(
+                &julia,
+                PICTURE_WIDTH,
+                PICTURE_HEIGHT,
+                4,
+                3,
+                seed,
+            )?)
+        }
+        None => None,
+    };
+    if let Some(cut) = cut {
+        cut.export(&dir)?;
+    }
+    let manifest = Manifest::load(&dir)?;
+    let puzzle =
+        Puzzle::from_file(File::open(dir.join(&manifest.puzzle)).map_err(PuzzleError::Read)?)?;
+    Ok((puzzle, manifest))
+}
+
+/// Where the engine looks for assets: next to `Cargo.toml` under cargo, otherwise next to the program
+fn assets_dir() -> PathBuf {
+    // bevy 의 AssetServer 도 같은 순서로 assets 폴더를 찾습니다.
+    std::env::var_os("CARGO_MANIFEST_DIR")
+        .map(PathBuf::from)
+        .or_else(|| {
+            std::env::current_exe()
+                .ok()
+                .and_then(|exe| exe.parent().map(Path::to_path_buf))
+        })
+        .unwrap_or_default()
+        .join("assets")
+}
+
+fn label(id: u16) -> String {
+    format!("piece{}", id)
+}
+
+/// Sprites turn counterclockwise, pieces turn clockwise
+fn sprite_rotation(rotation: Rotation) -> f32 {
+    -f32::from(rotation.quarters()) * FRAC_PI_2
+}
+
+fn game_logic(engine: &mut Engine, game_state: &mut GameState) {
+    if engine.keyboard_state.just_pressed(KeyCode::Q) {
+        engine.should_exit = true;
+    }
+    if game_state.solved {
+        return;
+    }
+    let Some(mouse) = engine.mouse_state.location() else {
+        return;
+    };
+    let cell = Vec2::new(
+        game_state.manifest.cell_width as f32,
+        game_state.manifest.cell_height as f32,
+    );
+
+    // 집기: 마우스 아래에 있는 조각 중 가장 위에 그려진 것을 집습니다.
+    // 보드에 놓여 있던 조각을 집으면 보드에서 빼냅니다.
+    if engine.mouse_state.just_pressed(MouseButton::Left) {
+        if let Some(id) = piece_under(engine, game_state, mouse, cell) {
+            if let Some((x, y)) = game_state.board.position_of(id) {
+                game_state.board.remove(x, y);
+            }
+            let sprite = engine.sprites.get_mut(&label(id)).unwrap();
+            sprite.layer = game_state.top_layer;
+            game_state.top_layer += 0.001;
+            game_state.held = Some((id, sprite.translation - mouse));
+        }
+    }
+
+    // 끌기
+    if let Some((id, offset)) = game_state.held {
+        if engine.mouse_state.pressed(MouseButton::Left) {
+            engine.sprites.get_mut(&label(id)).unwrap().translation = mouse + offset;
+        }
+    }
+
+    // 돌리기: 들고 있는 조각, 아니면 마우스 아래의 조각을 시계 방향으로 90도 돌립니다.
+    if engine.mouse_state.just_pressed(MouseButton::Right)
+        || engine.keyboard_state.just_pressed(KeyCode::Space)
+    {
+        let target = game_state
+            .held
+            .map(|(id, _)| id)
+            .or_else(|| piece_under(engine, game_state, mouse, cell))
+            .filter(|&id| game_state.board.position_of(id).is_none());
+        if let Some(id) = target {
+            let piece = game_state
+                .puzzle
+                .pieces
+                .iter_mut()
+                .find(|piece| piece.id == id)
+                .unwrap();
+            piece.rotate();
+            engine.sprites.get_mut(&label(id)).unwrap().rotation = sprite_rotation(piece.rotation);
+            engine.audio_manager.play_sfx(SfxPreset::Click, 0.3);
+        }
+    }
+
+    // 놓기: 칸 가까이에 놓으면 변이 맞는지 보드에 물어보고, 맞으면 칸에 딱 붙입니다.
+    if engine.mouse_state.just_released(MouseButton::Left) {
+        if let Some((id, _)) = game_state.held.take() {
+            drop_piece(engine, game_state, id, cell);
+        }
+    }
+
+    let status = engine.texts.get_mut("status").unwrap();
+    status.value = format!(
+        "{} of {} pieces placed. Drag to move, right click or space to turn, Q to quit",
+        game_state.board.placed(),
+        game_state.puzzle.num_pieces
+    );
+    if game_state.board.is_complete() {
+        game_state.solved = true;
+        status.value = format!("Solved {}! Well done.", game_state.puzzle.name);
+        engine
+            .audio_manager
+            .play_sfx(SfxPreset::Congratulations, 0.5);
+    }
+}
+
+/// The topmost piece whose cell-sized middle is under the mouse
+fn piece_under(engine: &Engine, game_state: &GameState, mouse: Vec2, cell: Vec2) -> Option<u16> {
+    game_state
+        .manifest
+        .pieces
+        .iter()
+        .filter_map(|entry| {
+            let sprite = engine.sprites.get(&label(entry.id))?;
+            let distance = (mouse - sprite.translation).abs();
+            // 돌아간 조각은 가로 세로가 바뀌지만, 가운데만 잡으면 되니 짧은 쪽으로 봅니다.
+            let reach = cell.min_element() / 2.0;
+            (distance.x <= reach && distance.y <= reach).then_some((entry.id, sprite.layer))
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(id, _)| id)
+}
+
+fn drop_piece(engine: &mut Engine, game_state: &mut GameState, id: u16, cell: Vec2) {
+    let sprite = engine.sprites.get_mut(&label(id)).unwrap();
+    // 화면 좌표는 y 가 위로 커지고, 퍼즐 좌표는 아래로 커집니다.
+    let grid = (sprite.translation - game_state.origin) * Vec2::new(1.0, -1.0) / cell;
+    let (x, y) = (grid.x.floor(), grid.y.floor());
+    let width = f32::from(game_state.manifest.width);
+    let height = f32::from(game_state.manifest.height);
+    if x < 0.0 || y < 0.0 || x >= width || y >= height {
+        return;
+    }
+    let center = game_state.origin + Vec2::new(x + 0.5, -(y + 0.5)) * cell;
+    if (sprite.translation - center).abs().cmpgt(cell * SNAP).any() {
+        return;
+    }
+
+    let piece = *game_state.puzzle.piece(id).unwrap();
+    // 그림이 옆으로 누운 채 들어가면 안 되니, 똑바로 세운 조각만 붙입니다.
+    let fitted =
+        piece.rotation == Rotation::R0 && game_state.board.place(piece, x as u16, y as u16).is_ok();
+    if fitted {
+        sprite.translation = center;
+        sprite.layer = BOARD_LAYER;
+        engine.audio_manager.play_sfx(SfxPreset::Confirmation1, 0.4);
+    } else {
+        engine.audio_manager.play_sfx(SfxPreset::Impact1, 0.2);
+    }
+}
@@ -0,0 +1,244 @@
+use std::fmt;
+
+/// Middle of each lane obstacles are spawned in, bottom to top
+pub const LANES: [f32; 5] = [-240.0, -120.0, 0.0, 120.0, 240.0];
+/// Cars steer this fast compared to the road, so they keep up as it speeds up
+const PLAYER_SPEED_RATIO: f32 = 0.625;
+
+/// How fast the race gets harder, picked on the title screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+/// The numbers behind a [`Difficulty`]
+struct Curve {
+    /// Road speed at the start, in pixels per second
+    start_speed: f32,
+    top_speed: f32,
+    /// How much faster the road gets every second
+    acceleration: f32,
+    /// Obstacles that can be on the road at the start, and at most
+    start_obstacles: usize,
+    max_obstacles: usize,
+    /// Seconds before one more obstacle can be on the road
+    seconds_per_obstacle: f32,
+    /// Road between one wave of obstacles and the next, in pixels
+    wave_gap: f32,
+    /// Seconds before waves of two or three obstacles start
+    groups_after: f32,
+    /// Seconds before walls with a single gap start
+    walls_after: f32,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard];
+
+    fn curve(self) -> Curve {
+        match self {
+            Difficulty::Easy => Curve {
+                start_speed: 300.0,
+                top_speed: 550.0,
+                acceleration: 4.0,
+                start_obstacles: 3,
+                max_obstacles: 6,
+                seconds_per_obstacle: 20.0,
+                wave_gap: 550.0,
+                groups_after: 20.0,
+                // 쉬움에서는 벽이 나오지 않습니다.
+                walls_after: f32::INFINITY,
+            },
+            Difficulty::Normal => Curve {
+                start_speed: 400.0,
+                top_speed: 750.0,
+                acceleration: 7.0,
+                start_obstacles: 3,
+                max_obstacles: 9,
+                seconds_per_obstacle: 12.0,
+                wave_gap: 450.0,
+                groups_after: 10.0,
+                walls_after: 40.0,
+            },
+            Difficulty::Hard => Curve {
+                start_speed: 500.0,
+                top_speed: 950.0,
+                acceleration: 10.0,
+                start_obstacles: 4,
+                max_obstacles: 12,
+                seconds_per_obstacle: 8.0,
+                wave_gap: 380.0,
+                groups_after: 0.0,
+                walls_after: 20.0,
+            },
+        }
+    }
+
+    /// How fast the road moves after `elapsed` seconds of racing
+    pub fn road_speed(self, elapsed: f32) -> f32 {
+        let curve = self.curve();
+        (curve.start_speed + curve.acceleration * elapsed).min(curve.top_speed)
+    }
+
+    /// How fast the cars steer after `elapsed` seconds of racing
+    pub fn player_speed(self, elapsed: f32) -> f32 {
+        self.road_speed(elapsed) * PLAYER_SPEED_RATIO
+    }
+
+    /// How many obstacles can be on the road at once after `elapsed` seconds
+    pub fn obstacles(self, elapsed: f32) -> usize {
+        let curve = self.curve();
+        let extra = (elapsed / curve.seconds_per_obstacle) as usize;
+        (curve.start_obstacles + extra).min(curve.max_obstacles)
+    }
+
+    /// Road between waves of obstacles, in pixels
+    pub fn wave_gap(self) -> f32 {
+        self.curve().wave_gap
+    }
+
+    /// The patterns a wave can be laid out in after `elapsed` seconds
+    pub fn patterns(self, elapsed: f32) -> Vec<Pattern> {
+        let curve = self.curve();
+        let mut patterns = vec![Pattern::Single];
+        if elapsed >= curve.groups_after {
+            patterns.extend([Pattern::Pair, Pattern::Stagger]);
+        }
+        if elapsed >= curve.walls_after {
+            patterns.push(Pattern::Wall);
+        }
+        patterns
+    }
+
+    /// One step harder, staying on [`Difficulty::Hard`]
+    pub fn harder(self) -> Difficulty {
+        Difficulty::ALL[(self as usize + 1).min(Difficulty::ALL.len() - 1)]
+    }
+
+    /// One step easier, staying on [`Difficulty::Easy`]
+    pub fn easier(self) -> Difficulty {
+        Difficulty::ALL[(self as usize).saturating_sub(1)]
+    }
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Normal => "normal",
+            Difficulty::Hard => "hard",
+        };
+        f.pad(name)
+    }
+}
+
+/// How one wave of obstacles is laid out across the lanes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pattern {
+    /// One obstacle
+    Single,
+    /// Two obstacles side by side in neighbouring lanes
+    Pair,
+    /// Every other lane, each a little further back than the last
+    Stagger,
+    /// Every lane but one
+    Wall,
+}
+
+impl Pattern {
+    /// Where each obstacle goes: an index into [`LANES`] and how far behind the front of the wave
+    ///
+    /// `pick` chooses the lanes, or the gap for a wall; any number will do.
+    pub fn placements(self, pick: usize) -> Vec<(usize, f32)> {
+        let lanes = LANES.len();
+        match self {
+            Pattern::Single => vec![(pick % lanes, 0.0)],
+            Pattern::Pair => {
+                let lane = pick % (lanes - 1);
+                vec![(lane, 0.0), (lane + 1, 0.0)]
+            }
+            Pattern::Stagger => (pick % 2..lanes)
+                .step_by(2)
+                .enumerate()
+                .map(|(i, lane)| (lane, 150.0 * i as f32))
+                .collect(),
+            Pattern::Wall => {
+                let gap = pick % lanes;
+                (0..lanes)
+                    .filter(|&lane| lane != gap)
+                    .map(|lane| (lane, 0.0))
+                    .collect()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn the_road_speeds_up_to_a_limit() {
+        for difficulty in Difficulty::ALL {
+            let start = difficulty.road_speed(0.0);
+            assert!(difficulty.road_speed(30.0) > start);
+            assert_eq!(difficulty.road_speed(1000.0), difficulty.road_speed(2000.0));
+            assert!(difficulty.player_speed(30.0) > difficulty.player_speed(0.0));
+        }
+        assert_eq!(Difficulty::Normal.road_speed(0.0), 400.0);
+        assert!(Difficulty::Easy.road_speed(60.0) < Difficulty::Hard.road_speed(60.0));
+    }
+
+    #[test]
+    fn more_obstacles_come_with_time() {
+        for difficulty in Difficulty::ALL {
+            assert!(difficulty.obstacles(0.0) >= 3);
+            assert!(difficulty.obstacles(60.0) > difficulty.obstacles(0.0));
+            assert_eq!(difficulty.obstacles(1000.0), difficulty.obstacles(2000.0));
+        }
+        assert!(Difficulty::Easy.obstacles(60.0) < Difficulty::Hard.obstacles(60.0));
+        // road_race 가 만들어 두는 장애물은 12 개입니다.
+        assert_eq!(Difficulty::Hard.obstacles(1000.0), 12);
+    }
+
+    #[test]
+    fn walls_only_come_later_and_never_on_easy() {
+        assert_eq!(Difficulty::Normal.patterns(0.0), vec![Pattern::Single]);
+        assert!(Difficulty::Normal.patterns(60.0).contains(&Pattern::Wall));
+        assert!(Difficulty::Hard.patterns(0.0).contains(&Pattern::Pair));
+        assert!(!Difficulty::Easy.patterns(1000.0).contains(&Pattern::Wall));
+    }
+
+    #[test]
+    fn patterns_stay_in_their_lanes() {
+        for pick in 0..20 {
+            for pattern in [
+                Pattern::Single,
+                Pattern::Pair,
+                Pattern::Stagger,
+                Pattern::Wall,
+            ] {
+                let placements = pattern.placements(pick);
+                assert!(placements.iter().all(|&(lane, _)| lane < LANES.len()));
+            }
+            // 벽에는 언제나 빠져나갈 틈이 한 곳 있습니다.
+            let wall = Pattern::Wall.placements(pick);
+            assert_eq!(wall.len(), LANES.len() - 1);
+            assert!(!wall.iter().any(|&(lane, _)| lane == pick % LANES.len()));
+        }
+        assert_eq!(Pattern::Pair.placements(3), vec![(3, 0.0), (4, 0.0)]);
+        assert_eq!(Pattern::Stagger.placements(1), vec![(1, 0.0), (3, 150.0)]);
+    }
+
+    #[test]
+    fn steps_between_difficulties() {
+        assert_eq!(Difficulty::default(), Difficulty::Normal);
+        assert_eq!(Difficulty::Normal.harder(), Difficulty::Hard);
+        assert_eq!(Difficulty::Hard.harder(), Difficulty::Hard);
+        assert_eq!(Difficulty::Normal.easier(), Difficulty::Easy);
+        assert_eq!(Difficulty::Easy.easier(), Difficulty::Easy);
+        assert_eq!(format!("{:<6}|", Difficulty::Hard), "hard  |");
+    }
+}
@@ -0,0 +1,172 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// How many scores the table keeps
+pub const TABLE_SIZE: usize = 10;
+/// How many letters of initials a score is signed with
+pub const INITIALS_LEN: usize = 3;
+
+/// One line of the table
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HighScore {
+    pub initials: String,
+    pub score: u32,
+}
+
+/// The best scores so far, highest first
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HighScores {
+    entries: Vec<HighScore>,
+}
+
+impl HighScores {
+    /// Read the table saved at `path`; a missing file is an empty table
+    ///
+    /// Lines that don't read as `INITIALS SCORE` are skipped, so a damaged
+    /// file loses only the scores on those lines.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e),
+        };
+        Ok(Self::parse(&text))
+    }
+
+    /// Write the table, one `INITIALS SCORE` per line
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.encode())
+    }
+
+    fn parse(text: &str) -> Self {
+        let mut table = Self::default();
+        for line in text.lines() {
+            let mut fields = line.split_whitespace();
+            let parsed = match (fields.next(), fields.next(), fields.next()) {
+                (Some(initials), Some(score), None) => score
+                    .parse()
+                    .ok()
+                    .filter(|_| valid_initials(initials))
+                    .map(|score| (initials, score)),
+                _ => None,
+            };
+            if let Some((initials, score)) = parsed {
+                table.insert(initials, score);
+            }
+        }
+        table
+    }
+
+    fn encode(&self) -> String {
+        self.entries
+            .iter()
+            .map(|entry| format!("{} {}\n", entry.initials, entry.score))
+            .collect()
+    }
+
+    /// Whether `score` would make it into the table
+    pub fn qualifies(&self, score: u32) -> bool {
+        score > 0
+            && (self.entries.len() < TABLE_SIZE
+                || self.entries.last().is_some_and(|last| score > last.score))
+    }
+
+    /// Add a score and return its place, counting from 0, if it made the table
+    ///
+    /// A new score goes below older scores that are just as high.
+    pub fn insert(&mut self, initials: &str, score: u32) -> Option<usize> {
+        if !self.qualifies(score) {
+            return None;
+        }
+        let place = self.entries.partition_point(|entry| entry.score >= score);
+        self.entries.insert(
+            place,
+            HighScore {
+                initials: initials.to_uppercase(),
+                score,
+            },
+        );
+        self.entries.truncate(TABLE_SIZE);
+        Some(place)
+    }
+}
+
+/// One to [`INITIALS_LEN`] ASCII letters
+pub fn valid_initials(initials: &str) -> bool {
+    (1..=INITIALS_LEN).contains(&initials.len())
+        && initials.chars().all(|c| c.is_ascii_alphabetic())
+}
+
+/// The whole table, one numbered line per score
+impl fmt::Display for HighScores {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "High scores")?;
+        if self.entries.is_empty() {
+            return write!(f, "\nnone yet");
+        }
+        for (place, entry) in self.entries.iter().enumerate() {
+            write!(
+                f,
+                "\n{:>2}. {:<3} {:>7}",
+                place + 1,
+                entry.initials,
+                entry.score
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn keeps_the_ten_best_in_order() {
+        let mut table = HighScores::default();
+        for score in 1..=12 {
+            table.insert("abc", score * 100);
+        }
+        assert_eq!(table.entries.len(), TABLE_SIZE);
+        assert_eq!(table.entries[0].score, 1200);
+        assert_eq!(table.entries[9].score, 300);
+        assert_eq!(table.entries[0].initials, "ABC");
+
+        assert!(!table.qualifies(300));
+        assert_eq!(table.insert("XY", 300), None);
+        // 같은 점수면 먼저 세운 기록이 위에 남습니다.
+        assert_eq!(table.insert("XY", 1100), Some(2));
+        assert_eq!(table.entries[1].initials, "ABC");
+        assert_eq!(table.entries[9].score, 400);
+        assert!(!HighScores::default().qualifies(0));
+    }
+
+    #[test]
+    fn survives_a_round_trip_and_skips_bad_lines() {
+        let dir = std::env::temp_dir().join(format!("road_race_scores_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("high_scores.txt");
+        assert_eq!(HighScores::load(&path).unwrap(), HighScores::default());
+
+        let mut table = HighScores::default();
+        table.insert("AAA", 900);
+        table.insert("B", 1500);
+        table.save(&path).unwrap();
+        assert_eq!(HighScores::load(&path).unwrap(), table);
+
+        let damaged = HighScores::parse("CCC 700\nnonsense\nTOOLONG 5\nDD -3\nEE 800 9\n");
+        assert_eq!(damaged.entries.len(), 1);
+        assert_eq!(damaged.entries[0].initials, "CCC");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn shows_a_numbered_table() {
+        assert_eq!(HighScores::default().to_string(), "High scores\nnone yet");
+        let mut table = HighScores::default();
+        table.insert("ab", 1234);
+        assert_eq!(table.to_string(), "High scores\n 1. AB     1234");
+    }
+}
@@ -1,46 +1,253 @@
+use difficulty::{Difficulty, LANES};
+use high_scores::{HighScores, INITIALS_LEN};
 use rand::prelude::*;
 use rusty_engine::prelude::*;
+use std::f32::consts::PI;
+use std::path::{Path, PathBuf};
 
-const PLAYER_SPEED: f32 = 250.0;
-const ROAD_SPEED: f32 = 400.0;
+mod difficulty;
+mod high_scores;
+
+const START_HEALTH: u8 = 3;
+/// Road covered, in pixels, for each point of score
+const PIXELS_PER_POINT: f32 = 10.0;
+/// How close, in pixels, an obstacle has to pass a car's middle without hitting it to count as a near miss
+const NEAR_MISS: f32 = 100.0;
+const NEAR_MISS_BONUS: u32 = 50;
+/// Seconds a car can't be hurt after hitting an obstacle
+const INVULNERABLE_SECONDS: f32 = 1.5;
+/// How long the car turns red or shows its own colour each time it blinks while it can't be hurt
+const BLINK_SECONDS: f32 = 0.1;
+/// Sideways speed the cars push each other apart with when they touch, in pixels per second
+const BUMP_SPEED: f32 = 500.0;
+/// How quickly a bump dies down; the share of the push lost every second
+const BUMP_DRAG: f32 = 4.0;
+/// How far up or down from the middle a car's middle can go
+const ROAD_EDGE: f32 = 320.0;
+/// Where the high score table is kept, next to `Cargo.toml` or the program
+const HIGH_SCORE_FILE: &str = "high_scores.txt";
+/// Keys that start a race from the title or game over screen
+const START_KEYS: [KeyCode; 2] = [KeyCode::Return, KeyCode::Space];
+// Esc 는 rusty_engine 이 창을 닫는 데 쓰니 여기에 넣지 않습니다.
+const PAUSE_KEYS: [KeyCode; 1] = [KeyCode::P];
+/// Obstacles in the order they join the race as it gets harder
+const OBSTACLE_POOL: [SpritePreset; 12] = [
+    SpritePreset::RacingBarrelBlue,
+    SpritePreset::RacingBarrelRed,
+    SpritePreset::RacingConeStraight,
+    SpritePreset::RacingBarrierRed,
+    SpritePreset::RacingCarGreen,
+    SpritePreset::RacingBarrelBlue,
+    SpritePreset::RacingBarrierWhite,
+    SpritePreset::RacingCarRed,
+    SpritePreset::RacingConeStraight,
+    SpritePreset::RacingCarYellow,
+    SpritePreset::RacingBarrelRed,
+    SpritePreset::RacingBarrierRed,
+];
+/// Where obstacles that aren't on the road wait, out of sight
+const PARKED_X: f32 = 2000.0;
+/// Where a wave of obstacles comes in, just past the right edge
+const SPAWN_X: f32 = 800.0;
+/// Keys that can be typed as initials
+const LETTERS: [(KeyCode, char); 26] = [
+    (KeyCode::A, 'A'),
+    (KeyCode::B, 'B'),
+    (KeyCode::C, 'C'),
+    (KeyCode::D, 'D'),
+    (KeyCode::E, 'E'),
+    (KeyCode::F, 'F'),
+    (KeyCode::G, 'G'),
+    (KeyCode::H, 'H'),
+    (KeyCode::I, 'I'),
+    (KeyCode::J, 'J'),
+    (KeyCode::K, 'K'),
+    (KeyCode::L, 'L'),
+    (KeyCode::M, 'M'),
+    (KeyCode::N, 'N'),
+    (KeyCode::O, 'O'),
+    (KeyCode::P, 'P'),
+    (KeyCode::Q, 'Q'),
+    (KeyCode::R, 'R'),
+    (KeyCode::S, 'S'),
+    (KeyCode::T, 'T'),
+    (KeyCode::U, 'U'),
+    (KeyCode::V, 'V'),
+    (KeyCode::W, 'W'),
+    (KeyCode::X, 'X'),
+    (KeyCode::Y, 'Y'),
+    (KeyCode::Z, 'Z'),
+];
+
+/// What the game is doing; each phase handles the keyboard on its own
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Phase {
+    /// Waiting for the first race to start
+    Title,
+    Playing,
+    /// Nothing moves until the pause key is pressed again
+    Paused,
+    /// The race is over and a player who made the high score table is typing their initials
+    NameEntry,
+    /// The road stops and the final stats stay up until a new race starts
+    GameOver,
+}
+
+/// One car and how its driver is doing
+#[derive(Debug, Clone)]
+struct Player {
+    /// Label of the car sprite
+    label: &'static str,
+    /// What the HUD and the winner announcement call the player
+    name: &'static str,
+    up: KeyCode,
+    down: KeyCode,
+    /// Where the car starts
+    start: Vec2,
+    /// Where the player's health and score are shown
+    hud: Vec2,
+    health: u8,
+    /// Seconds left before obstacles can hurt the car again
+    invulnerable: f32,
+    /// Sideways speed left over from bumping into the other car
+    bump: f32,
+    /// Road covered so far, in pixels
+    distance: f32,
+    /// Points for near misses
+    bonus: u32,
+}
+
+impl Player {
+    fn new(label: &'static str, name: &'static str, up: KeyCode, down: KeyCode) -> Self {
+        // player1 은 아래 차선에서, player2 는 위 차선에서 출발합니다.
+        let (start_y, hud_x) = if label == "player1" {
+            (-100.0, 450.0)
+        } else {
+            (100.0, -450.0)
+        };
+        Self {
+            label,
+            name,
+            up,
+            down,
+            start: Vec2::new(-500.0, start_y),
+            hud: Vec2::new(hud_x, 320.0),
+            health: START_HEALTH,
+            invulnerable: 0.0,
+            bump: 0.0,
+            distance: 0.0,
+            bonus: 0,
+        }
+    }
+
+    fn score(&self) -> u32 {
+        (self.distance / PIXELS_PER_POINT) as u32 + self.bonus
+    }
+
+    fn is_out(&self) -> bool {
+        self.health == 0
+    }
+
+    /// Label of the red car drawn over this one while it blinks
+    fn flash_label(&self) -> String {
+        format!("{}_flash", self.label)
+    }
+
+    fn hud_label(&self) -> String {
+        format!("{}_hud", self.label)
+    }
+
+    fn hud_text(&self) -> String {
+        format!(
+            "{}  Health: {}  Score: {}",
+            self.name,
+            self.health,
+            self.score()
+        )
+    }
+}
 
 struct GameState {
-    health_amount: u8,
-    lost: bool,
+    phase: Phase,
+    players: [Player; 2],
+    /// Picked on the title and game over screens, and kept between races
+    difficulty: Difficulty,
+    /// Seconds spent racing, not counting pauses
+    elapsed: f32,
+    /// Road left to cover, in pixels, before the next wave of obstacles comes in
+    until_wave: f32,
+    /// Obstacles that went through a car that couldn't be hurt, by obstacle and car label
+    hits: Vec<(String, &'static str)>,
+    high_scores: HighScores,
+    /// Players who made the high score table and still have to sign it, best first
+    signing: Vec<usize>,
+    /// What the player signing the table has typed so far
+    initials: String,
 }
 
 // GameState::default()
 impl Default for GameState {
     fn default() -> Self {
         Self {
-            health_amount: 3,
-            lost: false,
+            phase: Phase::Title,
+            players: [
+                Player::new("player1", "Blue", KeyCode::Up, KeyCode::Down),
+                Player::new("player2", "Black", KeyCode::W, KeyCode::S),
+            ],
+            difficulty: Difficulty::default(),
+            elapsed: 0.0,
+            until_wave: 0.0,
+            hits: Vec::new(),
+            high_scores: HighScores::default(),
+            signing: Vec::new(),
+            initials: String::new(),
         }
     }
 }
 
 fn main() {
     let mut game = Game::new();
+    // 점수표 파일이 없으면 빈 표로 시작하고, 읽을 수 없으면 알려만 주고 계속합니다.
+    let high_scores = HighScores::load(&high_score_path()).unwrap_or_else(|e| {
+        eprintln!("Couldn't read the high scores: {}", e);
+        HighScores::default()
+    });
+    let game_state = GameState {
+        high_scores,
+        ..Default::default()
+    };
 
-    let player1 = game.add_sprite("player1", SpritePreset::RacingCarBlue);
-
-    player1.translation.x = -500.0;
-    player1.translation.y = -100.0;
-    player1.layer = 10.0;
-    player1.collision = true;
-
-    let player2 = game.add_sprite("player2", SpritePreset::RacingCarBlack);
-    player2.translation.x = -500.0;
-    player2.translation.y = 100.0;
-    player2.layer = 10.0;
-    player2.collision = true;
-
-    game.audio_manager
-        .play_music(MusicPreset::WhimsicalPopsicle, 0.2);
+    // 차마다 화면 위쪽에 체력과 점수를 따로 보여 줍니다.
+    for (player, preset) in game_state
+        .players
+        .iter()
+        .zip([SpritePreset::RacingCarBlue, SpritePreset::RacingCarBlack])
+    {
+        let car = game.add_sprite(player.label, preset);
+        car.layer = 10.0;
+        car.collision = true;
+        // 깜빡일 때는 차를 줄이지 않고 빨간 차를 위에 겹쳐 그립니다.
+        // 크기를 바꾸면 충돌 범위도 같이 줄어 장애물이 그냥 지나가 버립니다.
+        let flash = game.add_sprite(player.flash_label(), SpritePreset::RacingCarRed);
+        flash.layer = 11.0;
+        flash.translation.x = PARKED_X;
+        let hud = game.add_text(player.hud_label(), "");
+        hud.translation = player.hud;
+        hud.font_size = 24.0;
+    }
 
-    // HP 상태 메세지
-    let health_message = game.add_text("health_message", "Health: 3");
-    health_message.translation = Vec2::new(550.0, 320.0);
+    // 화면 가운데에 띄우는 큰 글씨와 그 아래의 안내 문구
+    // 단계가 바뀔 때만 내용을 바꾸고, 경주 중에는 비워 둡니다.
+    let message = game.add_text("message", "Road Race");
+    message.font_size = 128.0;
+    message.translation.y = 200.0;
+    let prompt = game.add_text("prompt", title_prompt(game_state.difficulty));
+    prompt.translation.y = 110.0;
+    // 점수표는 타이틀 화면과 게임 오버 화면에만 보여 줍니다.
+    let table = game.add_text("high_scores", game_state.high_scores.to_string());
+    table.font = "font/FiraMono-Medium.ttf".to_string();
+    table.font_size = 20.0;
+    table.translation.y = -120.0;
 
     // 도로 그리기
     for i in 0..10 {
@@ -51,92 +258,442 @@ fn main() {
     }
 
     // 장애물
-    let obstacle_presets = vec![
-        SpritePreset::RacingBarrelBlue,
-        SpritePreset::RacingBarrelRed,
-        SpritePreset::RacingConeStraight,
-    ];
-
-    for (i, preset) in obstacle_presets.into_iter().enumerate() {
-        let obstacle = game.add_sprite(format!("obstacle{}", i), preset);
+    // 도로에 나올 차례가 될 때까지는 화면 밖에 세워 둡니다.
+    for (i, preset) in OBSTACLE_POOL.into_iter().enumerate() {
+        let obstacle = game.add_sprite(obstacle_label(i), preset);
         obstacle.layer = 5.0;
         obstacle.collision = true;
-        obstacle.translation.x = thread_rng().gen_range(800.0..1600.0);
-        obstacle.translation.y = thread_rng().gen_range(-300.0..300.0);
+        obstacle.translation.x = PARKED_X;
+        match preset {
+            // 가로막이는 한 차선에 들어가도록 줄입니다.
+            SpritePreset::RacingBarrierRed | SpritePreset::RacingBarrierWhite => {
+                obstacle.scale = 0.5
+            }
+            // 다른 차들은 마주 보고 달려옵니다.
+            SpritePreset::RacingCarGreen
+            | SpritePreset::RacingCarRed
+            | SpritePreset::RacingCarYellow => obstacle.rotation = PI,
+            _ => {}
+        }
     }
 
+    reset_players(&mut game, &game_state);
+
     game.add_logic(game_logic);
-    game.run(GameState::default());
+    game.run(game_state);
 }
 
 fn game_logic(engine: &mut Engine, game_state: &mut GameState) {
-    if game_state.health_amount == 0 {
-        let game_over = engine.add_text("game over", "Game Over");
-        game_over.font_size = 128.0;
-        engine.audio_manager.stop_music();
-        engine
-            .audio_manager
-            .play_sfx(SfxPreset::Confirmation1, 0.03);
+    // 이니셜을 치는 동안에는 Q 도 글자입니다.
+    if game_state.phase != Phase::NameEntry && engine.keyboard_state.just_pressed(KeyCode::Q) {
+        engine.should_exit = true;
     }
 
-    let mut p1_direction = 0.0;
-    let mut p2_direction = 0.0;
+    // 단계마다 받는 키가 다르고, 도로와 장애물은 Playing 일 때만 움직입니다.
+    match game_state.phase {
+        Phase::Title | Phase::GameOver => {
+            if engine.keyboard_state.just_pressed_any(&START_KEYS) {
+                start_race(engine, game_state);
+            } else {
+                pick_difficulty(engine, game_state);
+            }
+        }
+        Phase::NameEntry => enter_initials(engine, game_state),
+        Phase::Paused => {
+            if engine.keyboard_state.just_pressed_any(&PAUSE_KEYS) {
+                game_state.phase = Phase::Playing;
+                show_message(engine, "", "");
+            }
+        }
+        Phase::Playing => {
+            if engine.keyboard_state.just_pressed_any(&PAUSE_KEYS) {
+                game_state.phase = Phase::Paused;
+                show_message(engine, "Paused", "Press P to carry on");
+                return;
+            }
+            race(engine, game_state);
+            // 한 사람이라도 탈락하면 그 판은 끝납니다.
+            if game_state.players.iter().any(Player::is_out) {
+                game_over(engine, game_state);
+            }
+        }
+    }
+}
 
-    if engine.keyboard_state.pressed(KeyCode::Up) {
-        p1_direction += 1.0;
+/// Put everything back where it started and go
+fn start_race(engine: &mut Engine, game_state: &mut GameState) {
+    // 점수표와 난이도만 남기고 나머지는 모두 처음 상태로 돌립니다.
+    *game_state = GameState {
+        phase: Phase::Playing,
+        difficulty: game_state.difficulty,
+        high_scores: std::mem::take(&mut game_state.high_scores),
+        ..Default::default()
+    };
+    reset_players(engine, game_state);
+    for sprite in engine.sprites.values_mut() {
+        if sprite.label.starts_with("obstacle") {
+            sprite.translation.x = PARKED_X;
+        }
     }
+    // 지난 판의 마지막 프레임에 생긴 충돌이 새 판에 넘어오지 않게 비웁니다.
+    engine.collision_events.clear();
+
+    update_hud(engine, game_state);
+    show_message(engine, "", "");
+    engine.texts.get_mut("high_scores").unwrap().value.clear();
+    engine
+        .audio_manager
+        .play_music(MusicPreset::WhimsicalPopsicle, 0.2);
+}
+
+/// Freeze the road, then let anyone who made the high score table sign it
+fn game_over(engine: &mut Engine, game_state: &mut GameState) {
+    // 깜빡이던 차가 빨간 채로 멈추지 않게 합니다.
+    stop_blinking(engine, game_state);
+    engine.audio_manager.stop_music();
+    engine.audio_manager.play_sfx(SfxPreset::Confirmation1, 0.3);
+
+    let mut signing: Vec<usize> = (0..game_state.players.len()).collect();
+    signing.sort_by_key(|&i| std::cmp::Reverse(game_state.players[i].score()));
+    signing.retain(|&i| {
+        game_state
+            .high_scores
+            .qualifies(game_state.players[i].score())
+    });
+    game_state.signing = signing;
+    next_signature(engine, game_state);
+}
 
-    if engine.keyboard_state.pressed(KeyCode::Down) {
-        p1_direction -= 1.0;
+/// Ask the next player in line for their initials, or show the results when nobody is left
+fn next_signature(engine: &mut Engine, game_state: &mut GameState) {
+    game_state.initials.clear();
+    // 앞사람이 표에 들어가면서 뒷사람은 밀려났을 수 있으니 다시 확인합니다.
+    while let Some(&i) = game_state.signing.first() {
+        if game_state
+            .high_scores
+            .qualifies(game_state.players[i].score())
+        {
+            game_state.phase = Phase::NameEntry;
+            show_signing(engine, game_state);
+            return;
+        }
+        game_state.signing.remove(0);
     }
+    game_state.phase = Phase::GameOver;
+    show_results(engine, game_state);
+}
 
-    if engine.keyboard_state.pressed(KeyCode::W) {
-        p2_direction += 1.0;
+fn enter_initials(engine: &mut Engine, game_state: &mut GameState) {
+    for (key, letter) in LETTERS {
+        if engine.keyboard_state.just_pressed(key) && game_state.initials.len() < INITIALS_LEN {
+            game_state.initials.push(letter);
+        }
+    }
+    if engine.keyboard_state.just_pressed(KeyCode::Back) {
+        game_state.initials.pop();
+    }
+    if engine.keyboard_state.just_pressed(KeyCode::Return) && !game_state.initials.is_empty() {
+        let player = &game_state.players[game_state.signing.remove(0)];
+        game_state
+            .high_scores
+            .insert(&game_state.initials, player.score());
+        if let Err(e) = game_state.high_scores.save(&high_score_path()) {
+            eprintln!("Couldn't save the high scores: {}", e);
+        }
+        engine.audio_manager.play_sfx(SfxPreset::Jingle1, 0.4);
+        next_signature(engine, game_state);
+        return;
     }
+    show_signing(engine, game_state);
+}
+
+fn show_signing(engine: &mut Engine, game_state: &GameState) {
+    let player = &game_state.players[game_state.signing[0]];
+    let headline = format!("{} made the high scores!", player.name);
+    let prompt = format!(
+        "Score {}. Type your initials and press Enter: {:_<width$}",
+        player.score(),
+        game_state.initials,
+        width = INITIALS_LEN
+    );
+    show_message(engine, &headline, &prompt);
+    engine.texts.get_mut("high_scores").unwrap().value = game_state.high_scores.to_string();
+}
 
-    if engine.keyboard_state.pressed(KeyCode::S) {
-        p2_direction -= 1.0;
+/// Announce the winner and show the final scores and the high score table
+fn show_results(engine: &mut Engine, game_state: &GameState) {
+    let headline = match winner(&game_state.players) {
+        Some(player) => format!("{} wins!", player.name),
+        None => "It's a draw".to_string(),
+    };
+    let scores: Vec<String> = game_state
+        .players
+        .iter()
+        .map(|player| format!("{} {}", player.name, player.score()))
+        .collect();
+    let stats = format!(
+        "{} after {:.1} seconds on {}\n{}",
+        scores.join(", "),
+        game_state.elapsed,
+        game_state.difficulty,
+        again_prompt(game_state.difficulty)
+    );
+    show_message(engine, &headline, &stats);
+    engine.texts.get_mut("high_scores").unwrap().value = game_state.high_scores.to_string();
+}
+
+/// Left and right change the difficulty of the next race
+fn pick_difficulty(engine: &mut Engine, game_state: &mut GameState) {
+    let difficulty = if engine.keyboard_state.just_pressed(KeyCode::Left) {
+        game_state.difficulty.easier()
+    } else if engine.keyboard_state.just_pressed(KeyCode::Right) {
+        game_state.difficulty.harder()
+    } else {
+        return;
+    };
+    if difficulty == game_state.difficulty {
+        return;
+    }
+    game_state.difficulty = difficulty;
+    engine.audio_manager.play_sfx(SfxPreset::Click, 0.3);
+    match game_state.phase {
+        Phase::Title => {
+            engine.texts.get_mut("prompt").unwrap().value = title_prompt(difficulty);
+        }
+        _ => show_results(engine, game_state),
+    }
+}
+
+fn title_prompt(difficulty: Difficulty) -> String {
+    format!(
+        "Press Enter to start, P to pause, Q to quit\n< {} >  Left/Right to change",
+        difficulty
+    )
+}
+
+fn again_prompt(difficulty: Difficulty) -> String {
+    format!(
+        "Press Enter to race again  < {} >  Left/Right to change",
+        difficulty
+    )
+}
+
+/// The player still on the road, or the higher score if everyone went out at once
+fn winner(players: &[Player]) -> Option<&Player> {
+    let standing: Vec<&Player> = players.iter().filter(|player| !player.is_out()).collect();
+    let contenders = if standing.is_empty() {
+        players.iter().collect()
+    } else {
+        standing
+    };
+    let best = contenders.iter().map(|player| player.score()).max()?;
+    let mut leaders = contenders
+        .into_iter()
+        .filter(|player| player.score() == best);
+    // 점수까지 같으면 비긴 것으로 합니다.
+    match (leaders.next(), leaders.next()) {
+        (Some(player), None) => Some(player),
+        _ => None,
     }
+}
+
+/// One frame of racing: steer, count crashes and near misses, and scroll the road
+fn race(engine: &mut Engine, game_state: &mut GameState) {
+    game_state.elapsed += engine.delta_f32;
+    // 시간이 갈수록 도로가 빨라지고, 차도 그만큼 빨리 움직일 수 있습니다.
+    let road_speed = game_state.difficulty.road_speed(game_state.elapsed);
+    let player_speed = game_state.difficulty.player_speed(game_state.elapsed);
+
+    for player in &mut game_state.players {
+        let mut direction = 0.0;
+        if engine.keyboard_state.pressed(player.up) {
+            direction += 1.0;
+        }
+        if engine.keyboard_state.pressed(player.down) {
+            direction -= 1.0;
+        }
+        let car = engine.sprites.get_mut(player.label).unwrap();
+        let speed = direction * player_speed + player.bump;
+        car.translation.y =
+            (car.translation.y + speed * engine.delta_f32).clamp(-ROAD_EDGE, ROAD_EDGE);
+        car.rotation = direction * 0.15;
+        player.bump *= (1.0 - BUMP_DRAG * engine.delta_f32).max(0.0);
+        player.distance += road_speed * engine.delta_f32;
 
-    let player1 = engine.sprites.get_mut("player1").unwrap();
-    player1.translation.y += p1_direction * PLAYER_SPEED * engine.delta_f32;
-    player1.rotation = p1_direction * 0.15;
+        // 다칠 수 없는 동안에는 차가 깜빡입니다.
+        player.invulnerable = (player.invulnerable - engine.delta_f32).max(0.0);
+        let flashing = (player.invulnerable / BLINK_SECONDS) as u32 % 2 == 1;
+        let (translation, rotation) = (car.translation, car.rotation);
+        let flash = engine.sprites.get_mut(&player.flash_label()).unwrap();
+        if flashing {
+            flash.translation = translation;
+            flash.rotation = rotation;
+        } else {
+            flash.translation.x = PARKED_X;
+        }
+    }
 
-    let player2 = engine.sprites.get_mut("player2").unwrap();
-    player2.translation.y += p2_direction * PLAYER_SPEED * engine.delta_f32;
-    player2.rotation = p2_direction * 0.15;
+    // 충돌은 지난 프레임의 위치로 계산된 것이니, 장애물을 옮기기 전에 먼저 처리합니다.
+    for event in engine.collision_events.drain(..) {
+        if event.state.is_end() {
+            continue;
+        }
+        let [player1, player2] = &mut game_state.players;
+        // 두 차끼리 부딪히면 다치지 않고 서로 밀려납니다.
+        if event.pair.either_contains(player1.label) && event.pair.either_contains(player2.label) {
+            let below = engine.sprites[player1.label].translation.y
+                < engine.sprites[player2.label].translation.y;
+            let push = if below { -BUMP_SPEED } else { BUMP_SPEED };
+            player1.bump = push;
+            player2.bump = -push;
+            engine.audio_manager.play_sfx(SfxPreset::Impact1, 0.4);
+            continue;
+        }
+        for player in &mut game_state.players {
+            if !event.pair.either_contains(player.label) {
+                continue;
+            }
+            let obstacle = if event.pair.0 == player.label {
+                &event.pair.1
+            } else {
+                &event.pair.0
+            };
+            // 두 차가 같은 프레임에 한 장애물에 부딪히면, 먼저 처리된 차만 다칩니다.
+            // 그 차가 이미 장애물을 치워 두었으니 세워 둔 장애물은 건너뜁니다.
+            if engine.sprites[obstacle].translation.x >= PARKED_X {
+                continue;
+            }
+            // 막 부딪힌 차는 잠깐 동안 장애물을 그냥 지나칩니다.
+            // 스쳐 지나간 보너스는 주지 않도록 적어 둡니다.
+            if player.invulnerable > 0.0 || player.is_out() {
+                game_state.hits.push((obstacle.clone(), player.label));
+                continue;
+            }
+            player.health -= 1;
+            player.invulnerable = INVULNERABLE_SECONDS;
+            engine.audio_manager.play_sfx(SfxPreset::Impact3, 0.5);
+            // 부딪힌 장애물은 도로에서 치워서 두 번 부딪히지 않게 합니다.
+            engine.sprites.get_mut(obstacle).unwrap().translation.x = PARKED_X;
+            game_state.hits.retain(|(hit, _)| hit != obstacle);
+        }
+    }
 
+    let cars: Vec<Vec2> = game_state
+        .players
+        .iter()
+        .map(|player| engine.sprites[player.label].translation)
+        .collect();
     for sprite in engine.sprites.values_mut() {
         // 도로 움직이기
         if sprite.label.starts_with("road_line") {
-            sprite.translation.x -= ROAD_SPEED * engine.delta_f32;
+            sprite.translation.x -= road_speed * engine.delta_f32;
             if sprite.translation.x < -675.0 {
                 sprite.translation.x += 1500.0;
             }
         }
         // 장애물 움직이기
-        if sprite.label.starts_with("obstacle") {
-            sprite.translation.x -= ROAD_SPEED * engine.delta_f32;
+        // 세워 둔 장애물은 다음 물결에 나갈 때까지 그대로 둡니다.
+        if sprite.label.starts_with("obstacle") && sprite.translation.x < PARKED_X {
+            let before = sprite.translation.x;
+            sprite.translation.x -= road_speed * engine.delta_f32;
+            // 부딪히지 않고 차 가까이를 스쳐 지나가면 보너스를 줍니다.
+            // 다칠 수 없는 동안에는 부딪혀도 지나가니 보너스도 없습니다.
+            for (player, car) in game_state.players.iter_mut().zip(&cars) {
+                let passed = before >= car.x && sprite.translation.x < car.x;
+                let close = (sprite.translation.y - car.y).abs() < NEAR_MISS;
+                let hit = game_state
+                    .hits
+                    .iter()
+                    .any(|(obstacle, label)| *obstacle == sprite.label && *label == player.label);
+                if passed && close && !hit && player.invulnerable == 0.0 {
+                    player.bonus += NEAR_MISS_BONUS;
+                    engine.audio_manager.play_sfx(SfxPreset::Switch1, 0.3);
+                }
+            }
             if sprite.translation.x < -800.0 {
-                sprite.translation.x = thread_rng().gen_range(800.0..1600.0);
-                sprite.translation.y = thread_rng().gen_range(-300.0..300.0);
+                game_state
+                    .hits
+                    .retain(|(obstacle, _)| *obstacle != sprite.label);
+                sprite.translation.x = PARKED_X;
             }
         }
     }
 
-    let health_message = engine.texts.get_mut("health_message").unwrap();
-    for event in engine.collision_events.drain(..) {
-        // 이벤트의 pair에서 두개 중 하나도 player1이 아니거나 이벤트가 끝이 라면 continue
-        if !(event.pair.either_contains("player1") || event.pair.either_contains("player2"))
-            || event.state.is_end()
-        {
-            continue;
-        }
+    game_state.until_wave -= road_speed * engine.delta_f32;
+    if game_state.until_wave <= 0.0 {
+        spawn_wave(engine, game_state);
+        game_state.until_wave += game_state.difficulty.wave_gap();
+    }
 
-        if game_state.health_amount > 0 {
-            game_state.health_amount -= 1;
-            health_message.value = format!("Health: {}", game_state.health_amount);
-            engine.audio_manager.play_sfx(SfxPreset::Impact3, 0.5);
-        }
+    update_hud(engine, game_state);
+}
+
+/// Bring parked obstacles onto the road in one of the lane patterns unlocked so far
+fn spawn_wave(engine: &mut Engine, game_state: &GameState) {
+    let mut rng = thread_rng();
+    let difficulty = game_state.difficulty;
+    let pattern = *difficulty
+        .patterns(game_state.elapsed)
+        .choose(&mut rng)
+        .unwrap();
+    // 지금 나올 수 있는 장애물 가운데 세워 둔 것만 씁니다.
+    // 모자라면 있는 만큼만 내보내니, 벽에 틈이 더 생길 수 있습니다.
+    let mut parked: Vec<String> = (0..difficulty.obstacles(game_state.elapsed))
+        .map(obstacle_label)
+        .filter(|label| engine.sprites[label].translation.x >= PARKED_X)
+        .collect();
+    parked.shuffle(&mut rng);
+    for ((lane, offset), label) in pattern.placements(rng.gen()).into_iter().zip(parked) {
+        let sprite = engine.sprites.get_mut(&label).unwrap();
+        sprite.translation = Vec2::new(SPAWN_X + offset, LANES[lane]);
+    }
+}
+
+fn reset_players(engine: &mut Engine, game_state: &GameState) {
+    for player in &game_state.players {
+        let car = engine.sprites.get_mut(player.label).unwrap();
+        car.translation = player.start;
+        car.rotation = 0.0;
+    }
+    stop_blinking(engine, game_state);
+}
+
+/// Take the red cars drawn over blinking cars off the road
+fn stop_blinking(engine: &mut Engine, game_state: &GameState) {
+    for player in &game_state.players {
+        engine
+            .sprites
+            .get_mut(&player.flash_label())
+            .unwrap()
+            .translation
+            .x = PARKED_X;
+    }
+}
+
+fn update_hud(engine: &mut Engine, game_state: &GameState) {
+    for player in &game_state.players {
+        engine.texts.get_mut(&player.hud_label()).unwrap().value = player.hud_text();
     }
 }
+
+fn obstacle_label(i: usize) -> String {
+    format!("obstacle{}", i)
+}
+
+/// The high score file: next to `Cargo.toml` under cargo, otherwise next to the program
+fn high_score_path() -> PathBuf {
+    std::env::var_os("CARGO_MANIFEST_DIR")
+        .map(PathBuf::from)
+        .or_else(|| {
+            std::env::current_exe()
+                .ok()
+                .and_then(|exe| exe.parent().map(Path::to_path_buf))
+        })
+        .unwrap_or_default()
+        .join(HIGH_SCORE_FILE)
+}
+
+fn show_message(engine: &mut Engine, message: &str, prompt: &str) {
+    engine.texts.get_mut("message").unwrap().value = message.to_string();
+    engine.texts.get_mut("prompt").unwrap().value = prompt.to_string();
+}